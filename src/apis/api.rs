@@ -1,20 +1,35 @@
 use std::str::FromStr;
 
-use crate::apis::libs::{sdl::SdlApi, sfml::SfmlApi};
+use crate::apis::libs::headless::{HeadlessApi, HeadlessProp};
+#[cfg(feature = "sdl")]
+use crate::apis::libs::sdl::SdlApi;
+#[cfg(feature = "sfml")]
+use crate::apis::libs::sfml::SfmlApi;
 use crate::error::ChipError;
 use crate::models::api::Api;
 
 /// Public available implemented apis
+///
+/// The graphical ones depend on their cargo feature
 #[derive(Debug, Clone, Copy)]
 pub enum ApiKind {
+    #[cfg(feature = "sdl")]
     Sdl,
+    #[cfg(feature = "sfml")]
     Sfml,
+    Headless,
 }
 
 impl Default for ApiKind {
+    #[cfg(feature = "sdl")]
     fn default() -> Self {
         Self::Sdl
     }
+
+    #[cfg(not(feature = "sdl"))]
+    fn default() -> Self {
+        Self::Headless
+    }
 }
 
 impl FromStr for ApiKind {
@@ -22,8 +37,11 @@ impl FromStr for ApiKind {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let api = match s {
+            #[cfg(feature = "sdl")]
             "sdl" => Self::Sdl,
+            #[cfg(feature = "sfml")]
             "sfml" => Self::Sfml,
+            "headless" => Self::Headless,
             _ => {
                 return Err(ChipError::UseApi);
             }
@@ -40,6 +58,8 @@ pub struct GraphicProp {
     pub title: String,
     /// Window size
     pub size: (u32, u32),
+    /// Headless properties
+    pub headless: HeadlessProp,
 }
 
 // Window sizes
@@ -63,8 +83,11 @@ impl From<GraphicProp> for Box<dyn Api> {
         let (w, h) = prop.size;
 
         match prop.api {
+            #[cfg(feature = "sdl")]
             ApiKind::Sdl => Box::new(SdlApi::new(prop.title, w, h)),
+            #[cfg(feature = "sfml")]
             ApiKind::Sfml => Box::new(SfmlApi::new(prop.title, w, h)),
+            ApiKind::Headless => Box::new(HeadlessApi::new(w, h, prop.headless)),
         }
     }
 }
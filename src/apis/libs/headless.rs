@@ -0,0 +1,206 @@
+use std::{
+    any::Any,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    event::{Input, KeyEvent, KeyState},
+    models::{api::Api, audio::Audio},
+    properties::{
+        color::{Color, ColorPreset},
+        pattern::AudioPattern,
+        rectangle::Rectangle,
+    },
+};
+
+/// Inputs held from a given frame, until the next entry
+pub type InputScript = Vec<(u64, Vec<Input>)>;
+
+/// Headless properties
+#[derive(Default, Clone)]
+pub struct HeadlessProp {
+    /// Stop the run after this amount of frames
+    pub max_frames: Option<u64>,
+    /// Stop the run after this duration
+    pub timeout: Option<Duration>,
+    /// Scripted inputs
+    pub script: InputScript,
+}
+
+/// Windowless implementation, the framebuffer stays in memory
+pub struct HeadlessApi {
+    /// Properties
+    prop: HeadlessProp,
+    /// Drawing buffer
+    buffer: Vec<Color>,
+    /// Last displayed buffer
+    frame: Vec<Color>,
+    /// Window size
+    window_size: (u32, u32),
+    /// Scripted inputs held by the previous frame
+    keys: KeyState<Input>,
+    /// Window title
+    title: String,
+    /// Fullscreen mode
+    fullscreen: bool,
+    /// Number of `events` calls (aka frames)
+    frames: u64,
+    /// Run start
+    start: Instant,
+    /// Beep state
+    beeping: bool,
+    /// Beep sound pattern
+    pattern: Option<AudioPattern>,
+}
+
+impl HeadlessApi {
+    pub fn new(w: u32, h: u32, mut prop: HeadlessProp) -> Self {
+        let size = (w * h) as usize;
+
+        // Sorted by frame to make the lookup easier
+        prop.script.sort_by_key(|(frame, _)| *frame);
+
+        Self {
+            prop,
+            buffer: vec![ColorPreset::Black.into(); size],
+            frame: vec![ColorPreset::Black.into(); size],
+            window_size: (w, h),
+            keys: KeyState::default(),
+            title: String::new(),
+            fullscreen: false,
+            frames: 0,
+            start: Instant::now(),
+            beeping: false,
+            pattern: None,
+        }
+    }
+
+    /// Return the last displayed framebuffer
+    pub fn frame(&self) -> &[Color] {
+        &self.frame
+    }
+
+    /// Return the window title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Return the fullscreen mode
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Return the number of elapsed frames
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Return the beep state
+    pub fn is_beeping(&self) -> bool {
+        self.beeping
+    }
+
+    /// Return the beep sound pattern
+    pub fn pattern(&self) -> Option<AudioPattern> {
+        self.pattern
+    }
+
+    /// Return the scripted inputs for the current frame
+    fn scripted_inputs(&self) -> Vec<Input> {
+        self.prop
+            .script
+            .iter()
+            .rev()
+            .find(|(frame, _)| *frame <= self.frames)
+            .map(|(_, inputs)| inputs.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Api for HeadlessApi {
+    fn clear(&mut self) {
+        self.buffer.fill(ColorPreset::Black.into());
+    }
+
+    fn draw_rect(&mut self, rect: Rectangle, color: Color) {
+        let (w, h) = (self.window_size.0 as i32, self.window_size.1 as i32);
+
+        // Clip the rectangle to the window
+        let x_start = rect.x.clamp(0, w);
+        let y_start = rect.y.clamp(0, h);
+        let x_end = (rect.x + rect.w as i32).clamp(0, w);
+        let y_end = (rect.y + rect.h as i32).clamp(0, h);
+
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                self.buffer[(y * w + x) as usize] = color;
+            }
+        }
+    }
+
+    fn is_window_open(&self) -> bool {
+        if let Some(max_frames) = self.prop.max_frames {
+            if self.frames >= max_frames {
+                return false;
+            }
+        }
+
+        if let Some(timeout) = self.prop.timeout {
+            if self.start.elapsed() >= timeout {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn display(&mut self) {
+        self.frame.copy_from_slice(&self.buffer);
+    }
+
+    fn events(&mut self) -> Vec<KeyEvent<Input>> {
+        let inputs = self.scripted_inputs();
+
+        self.frames += 1;
+
+        self.keys.update(&inputs)
+    }
+
+    fn window_size(&self) -> (u32, u32) {
+        self.window_size
+    }
+
+    fn set_window_size(&mut self, (w, h): (u32, u32)) {
+        let size = (w * h) as usize;
+
+        self.buffer = vec![ColorPreset::Black.into(); size];
+        self.frame = vec![ColorPreset::Black.into(); size];
+        self.window_size = (w, h);
+    }
+
+    fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.fullscreen = fullscreen;
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl Audio for HeadlessApi {
+    fn resume_beep(&mut self) {
+        self.beeping = true;
+    }
+
+    fn pause_beep(&mut self) {
+        self.beeping = false;
+    }
+
+    fn set_pattern(&mut self, pattern: AudioPattern) {
+        self.pattern = Some(pattern);
+    }
+}
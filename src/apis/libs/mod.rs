@@ -1,4 +1,8 @@
+/// Headless (no window)
+pub mod headless;
 /// SDL2
+#[cfg(feature = "sdl")]
 pub mod sdl;
 /// SFML
+#[cfg(feature = "sfml")]
 pub mod sfml;
@@ -1,31 +1,58 @@
 use sdl2::audio::{AudioCallback, AudioDevice, AudioSpecDesired};
+use sdl2::controller::{Button, GameController};
 use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
 use sdl2::mouse::MouseButton;
-use sdl2::video::Window;
-use sdl2::Sdl;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::render::Texture;
+use sdl2::video::{FullscreenType, Window};
 use sdl2::{pixels::Color, rect::Rect, render::Canvas};
+use sdl2::{GameControllerSubsystem, Sdl};
 
+use std::any::Any;
 use std::collections::HashMap;
 
 use crate::models::audio::Audio;
 use crate::{
     apis::api::{WINDOW_MAX_H, WINDOW_MAX_W, WINDOW_MIN_H, WINDOW_MIN_W},
-    event::{Hotkey, Input, Mouse, MouseClick},
+    error::ChipError,
+    event::{GamepadButton, Hotkey, Input, KeyEvent, Mouse, MouseClick},
     models::api::Api,
-    properties::{color, rectangle::Rectangle},
+    properties::{color, pattern::AudioPattern, rectangle::Rectangle},
 };
 
 struct SquareWave {
     phase_inc: f32,
     phase: f32,
     volume: f32,
+    /// Samples per second
+    freq: f32,
+    /// Played instead of the square wave if any
+    pattern: Option<AudioPattern>,
+    /// Position in the pattern (in bits)
+    pattern_phase: f32,
 }
 
 impl AudioCallback for SquareWave {
     type Channel = f32;
 
     fn callback(&mut self, out: &mut [f32]) {
+        if let Some(pattern) = self.pattern {
+            let pattern_inc = pattern.rate() / self.freq;
+
+            // Play the pattern bits
+            for x in out.iter_mut() {
+                *x = if pattern.bit(self.pattern_phase as usize) {
+                    self.volume
+                } else {
+                    -self.volume
+                };
+                self.pattern_phase = (self.pattern_phase + pattern_inc) % 128.0;
+            }
+
+            return;
+        }
+
         // Generate a square wave
         for x in out.iter_mut() {
             *x = if self.phase <= 0.5 {
@@ -46,10 +73,22 @@ pub struct SdlApi {
     audio_device: AudioDevice<SquareWave>,
     /// Interacting with the window
     canvas: Canvas<Window>,
+    /// Streaming texture of the screen, recreated when the resolution changes
+    ///
+    /// Created by the canvas (`unsafe_textures`), it lives as long as the canvas
+    texture: Option<Texture>,
+    /// Pixels uploaded into the texture (RGB)
+    pixels: Vec<u8>,
     /// Used to keep the window open
     is_open: bool,
-    /// Used to handle multiple pressed keys continously
+    /// Held keys, to report the press and release edges once
     key_pressed: HashMap<sdl2::keyboard::Keycode, bool>,
+    /// Game controller subsystem, used to open the plugged controllers
+    controller_subsystem: GameControllerSubsystem,
+    /// Opened game controllers, by instance id
+    controllers: HashMap<u32, GameController>,
+    /// Held buttons, by controller instance id
+    button_pressed: HashMap<(u32, Button), bool>,
     /// Window size
     window_size: (u32, u32),
 }
@@ -76,16 +115,31 @@ impl SdlApi {
         let window_size = canvas.window().size();
         let audio_device = SdlApi::build_audio(&context);
 
+        // The plugged controllers are opened by the `ControllerDeviceAdded` events
+        let controller_subsystem = context.game_controller().unwrap();
+
         Self {
             context,
             audio_device,
             canvas,
+            texture: None,
+            pixels: Vec::new(),
             is_open: true,
             key_pressed: HashMap::new(),
+            controller_subsystem,
+            controllers: HashMap::new(),
+            button_pressed: HashMap::new(),
             window_size,
         }
     }
 
+    /// Return true if a controller holds the button
+    fn is_button_held(&self, button: Button) -> bool {
+        self.button_pressed
+            .iter()
+            .any(|((_, held), pressed)| *held == button && *pressed)
+    }
+
     /// Init audio
     fn build_audio(context: &Sdl) -> AudioDevice<SquareWave> {
         let audio_subsystem = context.audio().unwrap();
@@ -103,6 +157,9 @@ impl SdlApi {
                     phase_inc: 440.0 / spec.freq as f32,
                     phase: 0.0,
                     volume: 0.25,
+                    freq: spec.freq as f32,
+                    pattern: None,
+                    pattern_phase: 0.0,
                 }
             })
             .unwrap()
@@ -111,6 +168,7 @@ impl SdlApi {
 
 impl Api for SdlApi {
     fn clear(&mut self) {
+        self.canvas.set_draw_color(Color::BLACK);
         self.canvas.clear();
     }
 
@@ -121,6 +179,47 @@ impl Api for SdlApi {
         self.canvas.fill_rect(filled).unwrap();
     }
 
+    fn draw_frame(
+        &mut self,
+        size: (usize, usize),
+        pixels: &[color::Color],
+        area: Rectangle,
+    ) -> Result<(), ChipError> {
+        let (w, h) = (size.0 as u32, size.1 as u32);
+
+        // The resolution has changed
+        if self.texture.as_ref().is_none_or(|texture| {
+            let query = texture.query();
+
+            (query.width, query.height) != (w, h)
+        }) {
+            let texture = self
+                .canvas
+                .create_texture_streaming(PixelFormatEnum::RGB24, w, h)
+                .map_err(|e| ChipError::DrawScreen(e.to_string()))?;
+
+            if let Some(old) = self.texture.replace(texture) {
+                // Safety: the canvas owning the texture is still alive
+                unsafe { old.destroy() };
+            }
+        }
+
+        self.pixels.clear();
+        self.pixels
+            .extend(pixels.iter().flat_map(|color| [color.r, color.g, color.b]));
+
+        if let Some(texture) = self.texture.as_mut() {
+            texture
+                .update(None, &self.pixels, size.0 * 3)
+                .map_err(|e| ChipError::DrawScreen(e.to_string()))?;
+            self.canvas
+                .copy(texture, None, Some(area.into()))
+                .map_err(ChipError::DrawScreen)?;
+        }
+
+        Ok(())
+    }
+
     fn is_window_open(&self) -> bool {
         self.is_open
     }
@@ -129,8 +228,8 @@ impl Api for SdlApi {
         self.canvas.present();
     }
 
-    fn events(&mut self) -> Vec<Input> {
-        let mut inputs = Vec::<Input>::new();
+    fn events(&mut self) -> Vec<KeyEvent<Input>> {
+        let mut events = Vec::new();
         let mut event_pump = self.context.event_pump().unwrap();
 
         // Events handling
@@ -138,54 +237,124 @@ impl Api for SdlApi {
             match event {
                 Event::Quit { .. } => self.is_open = false,
 
-                // Hotkeys pressed
-                Event::KeyDown { keycode, .. } => {
-                    if keycode.is_some() {
-                        self.key_pressed.insert(keycode.unwrap(), true);
+                // Hotkeys pressed, without the repeated events
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } => {
+                    if self.key_pressed.insert(keycode, true) != Some(true) {
+                        events.push(KeyEvent::Down(Input::Hotkey(Hotkey::from(keycode))));
                     }
                 }
 
                 // Hotkeys released
-                Event::KeyUp { keycode, .. } => {
-                    if keycode.is_some() {
-                        self.key_pressed.insert(keycode.unwrap(), false);
+                Event::KeyUp {
+                    keycode: Some(keycode),
+                    ..
+                } => {
+                    if self.key_pressed.insert(keycode, false) == Some(true) {
+                        events.push(KeyEvent::Up(Input::Hotkey(Hotkey::from(keycode))));
                     }
                 }
 
                 // Handle the window events
                 Event::Window { win_event, .. } => {
-                    if let WindowEvent::Resized(w, h) = win_event {
+                    if let WindowEvent::Resized(w, h) | WindowEvent::SizeChanged(w, h) = win_event {
                         self.window_size = (w as u32, h as u32);
                     }
                 }
 
-                // Mouse buttons
+                // Game controllers hotplug
+                Event::ControllerDeviceAdded { which, .. } => {
+                    if let Ok(controller) = self.controller_subsystem.open(which) {
+                        self.controllers
+                            .insert(controller.instance_id(), controller);
+                    }
+                }
+
+                Event::ControllerDeviceRemoved { which, .. } => {
+                    self.controllers.remove(&which);
+
+                    // The buttons held while unplugging are released
+                    let held: Vec<Button> = self
+                        .button_pressed
+                        .iter()
+                        .filter(|((id, _), pressed)| *id == which && **pressed)
+                        .map(|((_, button), _)| *button)
+                        .collect();
+
+                    self.button_pressed.retain(|(id, _), _| *id != which);
+                    for button in held {
+                        if !self.is_button_held(button) {
+                            events.push(KeyEvent::Up(Input::Gamepad(button.into())));
+                        }
+                    }
+                }
+
+                // Game controller buttons, the same button may be held on several controllers
+                Event::ControllerButtonDown { which, button, .. } => {
+                    if !self.is_button_held(button) {
+                        events.push(KeyEvent::Down(Input::Gamepad(button.into())));
+                    }
+                    self.button_pressed.insert((which, button), true);
+                }
+
+                Event::ControllerButtonUp { which, button, .. } => {
+                    let pressed = self.button_pressed.insert((which, button), false);
+
+                    if pressed == Some(true) && !self.is_button_held(button) {
+                        events.push(KeyEvent::Up(Input::Gamepad(button.into())));
+                    }
+                }
+
+                // Mouse buttons, a click is pressed and released at once
                 Event::MouseButtonDown {
                     mouse_btn, x, y, ..
                 } => {
-                    let mouse = Mouse::new(mouse_btn, x, y);
+                    let mouse = Input::Mouse(Mouse::new(mouse_btn, x, y));
 
-                    inputs.push(Input::Mouse(mouse));
+                    events.push(KeyEvent::Down(mouse));
+                    events.push(KeyEvent::Up(mouse));
                 }
 
                 _ => {}
             }
         }
 
-        // Add pressed hotkeys
-        for kp in self.key_pressed.iter() {
-            if *kp.1 == true {
-                let hotkey = Hotkey::from(*kp.0);
+        events
+    }
 
-                inputs.push(Input::Hotkey(hotkey));
-            }
+    fn window_size(&self) -> (u32, u32) {
+        self.window_size
+    }
+
+    fn set_window_size(&mut self, (w, h): (u32, u32)) {
+        if let Err(e) = self.canvas.window_mut().set_size(w, h) {
+            eprintln!("{}", e);
         }
+        self.window_size = self.canvas.window().size();
+    }
 
-        inputs
+    fn set_fullscreen(&mut self, fullscreen: bool) {
+        let mode = match fullscreen {
+            true => FullscreenType::Desktop,
+            false => FullscreenType::Off,
+        };
+
+        if let Err(e) = self.canvas.window_mut().set_fullscreen(mode) {
+            eprintln!("{}", e);
+        }
+        self.window_size = self.canvas.window().size();
     }
 
-    fn window_size(&self) -> (u32, u32) {
-        self.window_size
+    fn set_title(&mut self, title: &str) {
+        if let Err(e) = self.canvas.window_mut().set_title(title) {
+            eprintln!("{}", e);
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
@@ -197,6 +366,10 @@ impl Audio for SdlApi {
     fn pause_beep(&mut self) {
         self.audio_device.pause();
     }
+
+    fn set_pattern(&mut self, pattern: AudioPattern) {
+        self.audio_device.lock().pattern = Some(pattern);
+    }
 }
 
 impl From<Rectangle> for Rect {
@@ -216,6 +389,29 @@ impl From<color::Color> for Color {
     }
 }
 
+impl From<Button> for GamepadButton {
+    fn from(button: Button) -> Self {
+        match button {
+            Button::A => Self::A,
+            Button::B => Self::B,
+            Button::X => Self::X,
+            Button::Y => Self::Y,
+            Button::Back => Self::Back,
+            Button::Guide => Self::Guide,
+            Button::Start => Self::Start,
+            Button::LeftStick => Self::LeftStick,
+            Button::RightStick => Self::RightStick,
+            Button::LeftShoulder => Self::LeftShoulder,
+            Button::RightShoulder => Self::RightShoulder,
+            Button::DPadUp => Self::DPadUp,
+            Button::DPadDown => Self::DPadDown,
+            Button::DPadLeft => Self::DPadLeft,
+            Button::DPadRight => Self::DPadRight,
+            _ => Self::Misc,
+        }
+    }
+}
+
 impl From<sdl2::keyboard::Keycode> for Hotkey {
     fn from(keycode: sdl2::keyboard::Keycode) -> Self {
         match keycode {
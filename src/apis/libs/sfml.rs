@@ -1,49 +1,488 @@
+use sfml::audio::{SoundStatus, SoundStream, SoundStreamPlayer};
+use sfml::graphics::{
+    Color, FloatRect, RectangleShape, RenderTarget, RenderWindow, Shape, Sprite, Texture,
+    Transformable, View,
+};
+use sfml::system::{Time, Vector2f};
+use sfml::window::{mouse::Button, ContextSettings, Event, Key, Style, VideoMode};
+use sfml::SfBox;
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::mem::ManuallyDrop;
+use std::sync::{Arc, Mutex};
+
 use crate::{
-    event::Input,
+    apis::api::{WINDOW_MAX_H, WINDOW_MAX_W, WINDOW_MIN_H, WINDOW_MIN_W},
+    error::ChipError,
+    event::{Hotkey, Input, KeyEvent, Mouse, MouseClick},
     models::{api::Api, audio::Audio},
-    properties::{color::Color, rectangle::Rectangle},
+    properties::{color, pattern::AudioPattern, rectangle::Rectangle},
 };
 
-pub struct SfmlApi {}
+/// Samples per second
+const SAMPLE_RATE: u32 = 44100;
+
+/// Square wave generator streamed to SFML
+struct SquareWave {
+    phase_inc: f32,
+    phase: f32,
+    volume: f32,
+    /// Chunk given to SFML
+    samples: Vec<i16>,
+    /// Played instead of the square wave if any, shared with the api
+    pattern: Arc<Mutex<Option<AudioPattern>>>,
+    /// Position in the pattern (in bits)
+    pattern_phase: f32,
+}
+
+impl SquareWave {
+    fn new(pattern: Arc<Mutex<Option<AudioPattern>>>) -> Self {
+        Self {
+            phase_inc: 440.0 / SAMPLE_RATE as f32,
+            phase: 0.0,
+            volume: 0.25,
+            samples: vec![0; 1024],
+            pattern,
+            pattern_phase: 0.0,
+        }
+    }
+}
+
+impl SoundStream for SquareWave {
+    fn get_data(&mut self) -> (&mut [i16], bool) {
+        let amplitude = (self.volume * i16::MAX as f32) as i16;
+
+        if let Some(pattern) = *self.pattern.lock().unwrap() {
+            let pattern_inc = pattern.rate() / SAMPLE_RATE as f32;
+
+            // Play the pattern bits
+            for x in self.samples.iter_mut() {
+                *x = if pattern.bit(self.pattern_phase as usize) {
+                    amplitude
+                } else {
+                    -amplitude
+                };
+                self.pattern_phase = (self.pattern_phase + pattern_inc) % 128.0;
+            }
+
+            return (&mut self.samples, true);
+        }
+
+        // Generate a square wave
+        for x in self.samples.iter_mut() {
+            *x = if self.phase <= 0.5 {
+                amplitude
+            } else {
+                -amplitude
+            };
+            self.phase = (self.phase + self.phase_inc) % 1.0;
+        }
+
+        (&mut self.samples, true)
+    }
+
+    fn seek(&mut self, _offset: Time) {
+        self.phase = 0.0;
+    }
+
+    fn channel_count(&self) -> u32 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+}
+
+/// SFML implementation
+pub struct SfmlApi {
+    /// Interacting with the window
+    window: RenderWindow,
+    /// Audio stream, dropped before its generator
+    player: ManuallyDrop<SoundStreamPlayer<'static, SquareWave>>,
+    /// Generator borrowed by the player, owned by the api and freed on drop
+    stream: *mut SquareWave,
+    /// Audio pattern, read by the generator
+    pattern: Arc<Mutex<Option<AudioPattern>>>,
+    /// Held keys, to report the press and release edges once
+    key_pressed: BTreeMap<Hotkey, bool>,
+    /// Texture of the screen, created again when the resolution changes
+    texture: SfBox<Texture>,
+    /// Pixels uploaded into the texture (RGBA)
+    pixels: Vec<u8>,
+    /// Window size
+    window_size: (u32, u32),
+    /// Window title, kept to create the window again
+    title: String,
+    /// Fullscreen mode
+    fullscreen: bool,
+    /// Window size before the fullscreen mode
+    windowed_size: (u32, u32),
+}
+
+/// Create a window, the hotkeys are tracked between the pressed and released events
+fn create_window<V: Into<VideoMode>>(mode: V, title: &str, style: Style) -> RenderWindow {
+    let mut window = RenderWindow::new(mode, title, style, &ContextSettings::default());
+
+    window.set_key_repeat_enabled(false);
+    window
+}
 
 impl SfmlApi {
-    pub fn new(_title: String, _w: u32, _h: u32) -> Self {
-        todo!()
+    pub fn new(title: String, w: u32, h: u32) -> Self {
+        let window = create_window((w, h), &title, Style::DEFAULT);
+        let size = window.size();
+        let pattern = Arc::new(Mutex::new(None));
+        let stream = Box::into_raw(Box::new(SquareWave::new(pattern.clone())));
+        // Safety: the generator is only freed on drop, after the player
+        let player = SoundStreamPlayer::new(unsafe { &mut *stream });
+
+        Self {
+            window,
+            player: ManuallyDrop::new(player),
+            stream,
+            pattern,
+            key_pressed: BTreeMap::new(),
+            texture: Texture::new().unwrap(),
+            pixels: Vec::new(),
+            window_size: (size.x, size.y),
+            title,
+            fullscreen: false,
+            windowed_size: (size.x, size.y),
+        }
+    }
+
+    /// Keep the window size in the limits and update the view
+    ///
+    /// The fullscreen window has the size of the desktop
+    fn resize(&mut self, w: u32, h: u32) {
+        let size = match self.fullscreen {
+            true => (w, h),
+            false => (
+                w.clamp(WINDOW_MIN_W, WINDOW_MAX_W),
+                h.clamp(WINDOW_MIN_H, WINDOW_MAX_H),
+            ),
+        };
+
+        if size != (w, h) {
+            self.window.set_size(size);
+        }
+
+        let area = FloatRect::new(0.0, 0.0, size.0 as f32, size.1 as f32);
+
+        self.window.set_view(&View::from_rect(area));
+        self.window_size = size;
+    }
+}
+
+impl Drop for SfmlApi {
+    fn drop(&mut self) {
+        // Safety: the player is stopped and dropped before freeing its generator,
+        // both are never used again
+        unsafe {
+            ManuallyDrop::drop(&mut self.player);
+            drop(Box::from_raw(self.stream));
+        }
     }
 }
 
 impl Api for SfmlApi {
     fn clear(&mut self) {
-        todo!()
+        self.window.clear(Color::BLACK);
     }
 
-    fn draw_rect(&mut self, _rect: Rectangle, _color: Color) {
-        todo!()
+    fn draw_rect(&mut self, rect: Rectangle, color: color::Color) {
+        let mut shape = RectangleShape::with_size(Vector2f::new(rect.w as f32, rect.h as f32));
+
+        shape.set_position((rect.x as f32, rect.y as f32));
+        shape.set_fill_color(color.into());
+
+        self.window.draw(&shape);
+    }
+
+    fn draw_frame(
+        &mut self,
+        size: (usize, usize),
+        pixels: &[color::Color],
+        area: Rectangle,
+    ) -> Result<(), ChipError> {
+        let (w, h) = (size.0 as u32, size.1 as u32);
+
+        // The resolution has changed
+        if self.texture.size() != (w, h).into() && !self.texture.create(w, h) {
+            return Err(ChipError::DrawScreen(format!(
+                "unable to create a {}x{} texture",
+                w, h
+            )));
+        }
+
+        self.pixels.clear();
+        self.pixels.extend(
+            pixels
+                .iter()
+                .flat_map(|color| [color.r, color.g, color.b, color.a]),
+        );
+
+        if self.pixels.len() != (w * h * 4) as usize {
+            return Err(ChipError::DrawScreen(format!(
+                "{} pixels for a {}x{} texture",
+                pixels.len(),
+                w,
+                h
+            )));
+        }
+
+        // Safety: there are `w * h` RGBA pixels, the size of the texture
+        unsafe { self.texture.update_from_pixels(&self.pixels, w, h, 0, 0) };
+
+        let mut sprite = Sprite::with_texture(&self.texture);
+
+        sprite.set_position((area.x as f32, area.y as f32));
+        sprite.set_scale((area.w as f32 / w as f32, area.h as f32 / h as f32));
+
+        self.window.draw(&sprite);
+
+        Ok(())
     }
 
     fn is_window_open(&self) -> bool {
-        todo!()
+        self.window.is_open()
     }
 
     fn display(&mut self) {
-        todo!()
+        self.window.display();
     }
 
-    fn events(&mut self) -> Vec<Input> {
-        todo!()
+    fn events(&mut self) -> Vec<KeyEvent<Input>> {
+        let mut events = Vec::new();
+
+        // Events handling
+        while let Some(event) = self.window.poll_event() {
+            match event {
+                Event::Closed => self.window.close(),
+
+                // Hotkeys pressed
+                Event::KeyPressed { code, .. } => {
+                    if let Some(hotkey) = to_hotkey(code) {
+                        if self.key_pressed.insert(hotkey, true) != Some(true) {
+                            events.push(KeyEvent::Down(Input::Hotkey(hotkey)));
+                        }
+                    }
+                }
+
+                // Hotkeys released
+                Event::KeyReleased { code, .. } => {
+                    if let Some(hotkey) = to_hotkey(code) {
+                        if self.key_pressed.insert(hotkey, false) == Some(true) {
+                            events.push(KeyEvent::Up(Input::Hotkey(hotkey)));
+                        }
+                    }
+                }
+
+                // Handle the window events
+                Event::Resized { width, height } => self.resize(width, height),
+
+                // Mouse buttons, a click is pressed and released at once
+                Event::MouseButtonPressed { button, x, y } => {
+                    let mouse = Input::Mouse(Mouse::new(button, x, y));
+
+                    events.push(KeyEvent::Down(mouse));
+                    events.push(KeyEvent::Up(mouse));
+                }
+
+                _ => {}
+            }
+        }
+
+        events
     }
 
     fn window_size(&self) -> (u32, u32) {
-        todo!()
+        self.window_size
+    }
+
+    fn set_window_size(&mut self, (w, h): (u32, u32)) {
+        if self.fullscreen {
+            return;
+        }
+
+        self.window.set_size((w, h));
+        self.resize(w, h);
+    }
+
+    fn set_fullscreen(&mut self, fullscreen: bool) {
+        if fullscreen == self.fullscreen {
+            return;
+        }
+
+        // The window is created again with the new style
+        self.window = match fullscreen {
+            true => {
+                self.windowed_size = self.window_size;
+                create_window(VideoMode::desktop_mode(), &self.title, Style::FULLSCREEN)
+            }
+            false => create_window(self.windowed_size, &self.title, Style::DEFAULT),
+        };
+        self.fullscreen = fullscreen;
+
+        let size = self.window.size();
+
+        self.resize(size.x, size.y);
+    }
+
+    fn set_title(&mut self, title: &str) {
+        self.window.set_title(title);
+        self.title = title.to_string();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
     }
 }
 
 impl Audio for SfmlApi {
     fn resume_beep(&mut self) {
-        todo!()
+        if self.player.status() != SoundStatus::PLAYING {
+            self.player.play();
+        }
     }
 
     fn pause_beep(&mut self) {
-        todo!()
+        if self.player.status() == SoundStatus::PLAYING {
+            self.player.pause();
+        }
+    }
+
+    fn set_pattern(&mut self, pattern: AudioPattern) {
+        *self.pattern.lock().unwrap() = Some(pattern);
     }
 }
+
+impl From<color::Color> for Color {
+    fn from(c: color::Color) -> Self {
+        Self::rgba(c.r, c.g, c.b, c.a)
+    }
+}
+
+impl From<Button> for MouseClick {
+    fn from(button: Button) -> Self {
+        match button {
+            Button::Left => Self::Left,
+            Button::Middle => Self::Middle,
+            Button::Right => Self::Right,
+            Button::XButton1 => Self::Unknown,
+            Button::XButton2 => Self::Unknown,
+        }
+    }
+}
+
+/// Convert a SFML key into a hotkey, SFML knows less keys than SDL
+fn to_hotkey(key: Key) -> Option<Hotkey> {
+    let hotkey = match key {
+        Key::A => Hotkey::A,
+        Key::B => Hotkey::B,
+        Key::C => Hotkey::C,
+        Key::D => Hotkey::D,
+        Key::E => Hotkey::E,
+        Key::F => Hotkey::F,
+        Key::G => Hotkey::G,
+        Key::H => Hotkey::H,
+        Key::I => Hotkey::I,
+        Key::J => Hotkey::J,
+        Key::K => Hotkey::K,
+        Key::L => Hotkey::L,
+        Key::M => Hotkey::M,
+        Key::N => Hotkey::N,
+        Key::O => Hotkey::O,
+        Key::P => Hotkey::P,
+        Key::Q => Hotkey::Q,
+        Key::R => Hotkey::R,
+        Key::S => Hotkey::S,
+        Key::T => Hotkey::T,
+        Key::U => Hotkey::U,
+        Key::V => Hotkey::V,
+        Key::W => Hotkey::W,
+        Key::X => Hotkey::X,
+        Key::Y => Hotkey::Y,
+        Key::Z => Hotkey::Z,
+        Key::Num0 => Hotkey::Num0,
+        Key::Num1 => Hotkey::Num1,
+        Key::Num2 => Hotkey::Num2,
+        Key::Num3 => Hotkey::Num3,
+        Key::Num4 => Hotkey::Num4,
+        Key::Num5 => Hotkey::Num5,
+        Key::Num6 => Hotkey::Num6,
+        Key::Num7 => Hotkey::Num7,
+        Key::Num8 => Hotkey::Num8,
+        Key::Num9 => Hotkey::Num9,
+        Key::Escape => Hotkey::Escape,
+        Key::LControl => Hotkey::LCtrl,
+        Key::LShift => Hotkey::LShift,
+        Key::LAlt => Hotkey::LAlt,
+        Key::LSystem => Hotkey::LGui,
+        Key::RControl => Hotkey::RCtrl,
+        Key::RShift => Hotkey::RShift,
+        Key::RAlt => Hotkey::RAlt,
+        Key::RSystem => Hotkey::RGui,
+        Key::Menu => Hotkey::Menu,
+        Key::LBracket => Hotkey::LeftBracket,
+        Key::RBracket => Hotkey::RightBracket,
+        Key::Semicolon => Hotkey::Semicolon,
+        Key::Comma => Hotkey::Comma,
+        Key::Period => Hotkey::Period,
+        Key::Quote => Hotkey::Quote,
+        Key::Slash => Hotkey::Slash,
+        Key::Backslash => Hotkey::Backslash,
+        Key::Tilde => Hotkey::Backquote,
+        Key::Equal => Hotkey::Equals,
+        Key::Hyphen => Hotkey::Minus,
+        Key::Space => Hotkey::Space,
+        Key::Enter => Hotkey::Return,
+        Key::Backspace => Hotkey::Backspace,
+        Key::Tab => Hotkey::Tab,
+        Key::PageUp => Hotkey::PageUp,
+        Key::PageDown => Hotkey::PageDown,
+        Key::End => Hotkey::End,
+        Key::Home => Hotkey::Home,
+        Key::Insert => Hotkey::Insert,
+        Key::Delete => Hotkey::Delete,
+        Key::Add => Hotkey::KpPlus,
+        Key::Subtract => Hotkey::KpMinus,
+        Key::Multiply => Hotkey::KpMultiply,
+        Key::Divide => Hotkey::KpDivide,
+        Key::Left => Hotkey::Left,
+        Key::Right => Hotkey::Right,
+        Key::Up => Hotkey::Up,
+        Key::Down => Hotkey::Down,
+        Key::Numpad0 => Hotkey::Kp0,
+        Key::Numpad1 => Hotkey::Kp1,
+        Key::Numpad2 => Hotkey::Kp2,
+        Key::Numpad3 => Hotkey::Kp3,
+        Key::Numpad4 => Hotkey::Kp4,
+        Key::Numpad5 => Hotkey::Kp5,
+        Key::Numpad6 => Hotkey::Kp6,
+        Key::Numpad7 => Hotkey::Kp7,
+        Key::Numpad8 => Hotkey::Kp8,
+        Key::Numpad9 => Hotkey::Kp9,
+        Key::F1 => Hotkey::F1,
+        Key::F2 => Hotkey::F2,
+        Key::F3 => Hotkey::F3,
+        Key::F4 => Hotkey::F4,
+        Key::F5 => Hotkey::F5,
+        Key::F6 => Hotkey::F6,
+        Key::F7 => Hotkey::F7,
+        Key::F8 => Hotkey::F8,
+        Key::F9 => Hotkey::F9,
+        Key::F10 => Hotkey::F10,
+        Key::F11 => Hotkey::F11,
+        Key::F12 => Hotkey::F12,
+        Key::F13 => Hotkey::F13,
+        Key::F14 => Hotkey::F14,
+        Key::F15 => Hotkey::F15,
+        Key::Pause => Hotkey::Pause,
+        _ => return None,
+    };
+
+    Some(hotkey)
+}
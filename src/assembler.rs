@@ -0,0 +1,455 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::ChipError,
+    interpreters::{
+        pc::{OPCODE_SIZE, PROGRAM_START},
+        types::InterpreterType,
+    },
+};
+
+/// Comment delimiter
+const COMMENT: char = ';';
+
+/// Number or label, labels are resolved in the second pass
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(u32),
+    Label(String),
+}
+
+/// Instruction operand
+#[derive(Debug, Clone)]
+enum Operand {
+    /// Vx
+    Register(u16),
+    /// Vx-Vy
+    Range(u16, u16),
+    /// byte, nibble, addr or label
+    Value(Expr),
+    /// long nnnn
+    Long(Expr),
+    /// I
+    I,
+    /// [I]
+    IndirectI,
+    /// DT
+    Dt,
+    /// ST
+    St,
+    /// K
+    K,
+    /// F
+    F,
+    /// HF
+    Hf,
+    /// B
+    B,
+    /// R
+    R,
+}
+
+/// A parsed source line, without its label
+#[derive(Debug)]
+struct Statement {
+    /// Source line number (starts at 1)
+    line: usize,
+    /// Lowercase instruction or directive
+    name: String,
+    /// Operands
+    operands: Vec<Operand>,
+}
+
+/// Parse a number, hexadecimal with `0x`, binary with `0b`, decimal otherwise
+fn parse_number(value: &str) -> Option<u32> {
+    let lower = value.to_lowercase();
+
+    if let Some(hex) = lower.strip_prefix("0x") {
+        u32::from_str_radix(hex, 16).ok()
+    } else if let Some(bin) = lower.strip_prefix("0b") {
+        u32::from_str_radix(bin, 2).ok()
+    } else {
+        lower.parse().ok()
+    }
+}
+
+/// Return true if `name` is a valid label name
+fn is_label(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parse a register name (V0 - VF)
+fn parse_register(value: &str) -> Option<u16> {
+    let lower = value.to_lowercase();
+    let index = lower.strip_prefix('v')?;
+
+    match index.len() {
+        1 => u16::from_str_radix(index, 16).ok(),
+        _ => None,
+    }
+}
+
+/// Parse a number or a label
+fn parse_value(value: &str) -> Result<Expr, String> {
+    if let Some(number) = parse_number(value) {
+        Ok(Expr::Number(number))
+    } else if is_label(value) {
+        Ok(Expr::Label(value.to_string()))
+    } else {
+        Err(format!("invalid value `{}`", value))
+    }
+}
+
+/// Parse an operand
+fn parse_operand(value: &str) -> Result<Operand, String> {
+    let lower = value.to_lowercase();
+
+    let operand = match lower.as_str() {
+        "i" => Operand::I,
+        "[i]" => Operand::IndirectI,
+        "dt" => Operand::Dt,
+        "st" => Operand::St,
+        "k" => Operand::K,
+        "f" => Operand::F,
+        "hf" => Operand::Hf,
+        "b" => Operand::B,
+        "r" => Operand::R,
+        _ => {
+            if let Some(register) = parse_register(value) {
+                Operand::Register(register)
+            } else if let Some((x, y)) = value.split_once('-') {
+                match (parse_register(x.trim()), parse_register(y.trim())) {
+                    (Some(x), Some(y)) => Operand::Range(x, y),
+                    _ => return Err(format!("invalid register range `{}`", value)),
+                }
+            } else if lower.starts_with("long ") {
+                Operand::Long(parse_value(value[5..].trim())?)
+            } else {
+                Operand::Value(parse_value(value)?)
+            }
+        }
+    };
+
+    Ok(operand)
+}
+
+/// Turns a mnemonic source into a program
+///
+/// The syntax is the one of `models::instructions`, case insensitive, with:
+/// - labels : `name:`, usable as an address
+/// - `db byte, ...` and `dw word, ...` data directives
+/// - `org addr` to move the current address forward
+/// - comments starting with `;`
+pub struct Assembler {
+    /// Interpreter type, for the SUPER-CHIP and XO-CHIP instructions
+    kind: InterpreterType,
+}
+
+impl Assembler {
+    pub fn new(kind: InterpreterType) -> Self {
+        Self { kind }
+    }
+
+    /// Build the error of the line `line`
+    fn error<S: Into<String>>(line: usize, message: S) -> ChipError {
+        ChipError::Assemble {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Parse the source, return the statements and the labels address
+    fn parse(&self, source: &str) -> Result<(Vec<Statement>, HashMap<String, u32>), ChipError> {
+        let mut statements = Vec::new();
+        let mut labels = HashMap::new();
+        let mut addr = PROGRAM_START as u32;
+
+        for (index, text) in source.lines().enumerate() {
+            let line = index + 1;
+            let mut text = text.split(COMMENT).next().unwrap_or_default().trim();
+
+            // Label(s)
+            while let Some((label, rest)) = text.split_once(':') {
+                let label = label.trim();
+
+                // Registers and keywords are reserved
+                if !is_label(label) || !matches!(parse_operand(label), Ok(Operand::Value(_))) {
+                    return Err(Self::error(line, format!("invalid label `{}`", label)));
+                }
+                if labels.insert(label.to_string(), addr).is_some() {
+                    return Err(Self::error(line, format!("duplicate label `{}`", label)));
+                }
+                text = rest.trim();
+            }
+
+            if text.is_empty() {
+                continue;
+            }
+
+            let (name, operands) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+            let operands = match operands.trim().is_empty() {
+                true => Vec::new(),
+                false => operands
+                    .split(',')
+                    .map(str::trim)
+                    .map(|operand| match operand.is_empty() {
+                        true => Err("empty operand".to_string()),
+                        false => parse_operand(operand),
+                    })
+                    .collect::<Result<Vec<Operand>, String>>()
+                    .map_err(|e| Self::error(line, e))?,
+            };
+            let statement = Statement {
+                line,
+                name: name.to_lowercase(),
+                operands,
+            };
+
+            addr = match statement.name.as_str() {
+                "org" => match statement.operands.as_slice() {
+                    [Operand::Value(Expr::Number(value))] if *value >= addr => *value,
+                    [Operand::Value(Expr::Number(_))] => {
+                        return Err(Self::error(line, "org can not move backward"))
+                    }
+                    _ => return Err(Self::error(line, "org expects an address")),
+                },
+                _ => addr + self.size(&statement) as u32,
+            };
+
+            if addr > self.memory_size() {
+                return Err(Self::error(line, "the program does not fit in memory"));
+            }
+
+            statements.push(statement);
+        }
+
+        Ok((statements, labels))
+    }
+
+    /// Return the memory size of the interpreter
+    fn memory_size(&self) -> u32 {
+        match self.kind {
+            InterpreterType::Xochip => 0x10000,
+            _ => 0x1000,
+        }
+    }
+
+    /// Return the size in bytes of a statement
+    fn size(&self, statement: &Statement) -> usize {
+        match statement.name.as_str() {
+            "db" => statement.operands.len(),
+            "dw" => statement.operands.len() * 2,
+            _ if matches!(statement.operands.last(), Some(Operand::Long(_))) => {
+                OPCODE_SIZE as usize * 2
+            }
+            _ => OPCODE_SIZE as usize,
+        }
+    }
+
+    /// Return an error if the SUPER-CHIP instructions are not supported
+    fn require_super(&self, name: &str) -> Result<(), String> {
+        match self.kind {
+            InterpreterType::Original => Err(format!(
+                "`{}` needs the schip or xochip interpreter",
+                name.to_uppercase()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Return an error if the XO-CHIP instructions are not supported
+    fn require_xo(&self, name: &str) -> Result<(), String> {
+        match self.kind {
+            InterpreterType::Xochip => Ok(()),
+            _ => Err(format!(
+                "`{}` needs the xochip interpreter",
+                name.to_uppercase()
+            )),
+        }
+    }
+
+    /// Encode a statement
+    fn encode(
+        &self,
+        statement: &Statement,
+        labels: &HashMap<String, u32>,
+    ) -> Result<Vec<u8>, String> {
+        use Operand::*;
+
+        // Resolve a value, it must be lower or equal to `max`
+        let value = |value: &Expr, max: u32| -> Result<u16, String> {
+            let number = match value {
+                Expr::Number(number) => *number,
+                Expr::Label(label) => *labels
+                    .get(label)
+                    .ok_or_else(|| format!("unknown label `{}`", label))?,
+            };
+
+            if number > max {
+                return Err(format!("{:#x} is greater than {:#x}", number, max));
+            }
+
+            Ok(number as u16)
+        };
+        let (nibble, byte, addr, word) = (0xf, 0xff, 0xfff, 0xffff);
+        let name = statement.name.as_str();
+
+        let opcode = match (name, statement.operands.as_slice()) {
+            ("db", values) => {
+                return values
+                    .iter()
+                    .map(|operand| match operand {
+                        Value(v) => value(v, byte).map(|b| b as u8),
+                        _ => Err(String::from("db expects bytes")),
+                    })
+                    .collect();
+            }
+            ("dw", values) => {
+                return values
+                    .iter()
+                    .map(|operand| match operand {
+                        Value(v) => value(v, word).map(u16::to_be_bytes),
+                        _ => Err(String::from("dw expects words")),
+                    })
+                    .collect::<Result<Vec<[u8; 2]>, String>>()
+                    .map(|words| words.concat());
+            }
+            ("org", _) => return Ok(Vec::new()),
+            ("cls", []) => 0x00e0,
+            ("ret", []) => 0x00ee,
+            ("scd", [Value(n)]) => {
+                self.require_super(name)?;
+                0x00c0 | value(n, nibble)?
+            }
+            ("scu", [Value(n)]) => {
+                self.require_xo(name)?;
+                0x00d0 | value(n, nibble)?
+            }
+            ("scr" | "scl" | "exit" | "low" | "high", []) => {
+                self.require_super(name)?;
+                match name {
+                    "scr" => 0x00fb,
+                    "scl" => 0x00fc,
+                    "exit" => 0x00fd,
+                    "low" => 0x00fe,
+                    _ => 0x00ff,
+                }
+            }
+            ("sys", [Value(a)]) => value(a, addr)?,
+            ("jp", [Value(a)]) => 0x1000 | value(a, addr)?,
+            ("jp", [Register(0), Value(a)]) => 0xb000 | value(a, addr)?,
+            // Bxnn, the jump quirk of SCHIP and XO-CHIP
+            ("jp", [Register(x), Value(nn)]) => {
+                self.require_super(name)?;
+                0xb000 | x << 8 | value(nn, byte)?
+            }
+            ("call", [Value(a)]) => 0x2000 | value(a, addr)?,
+            ("se", [Register(x), Value(kk)]) => 0x3000 | x << 8 | value(kk, byte)?,
+            ("sne", [Register(x), Value(kk)]) => 0x4000 | x << 8 | value(kk, byte)?,
+            ("se", [Register(x), Register(y)]) => 0x5000 | x << 8 | y << 4,
+            ("sne", [Register(x), Register(y)]) => 0x9000 | x << 8 | y << 4,
+            ("ld", [IndirectI, Range(x, y)]) => {
+                self.require_xo(name)?;
+                0x5002 | x << 8 | y << 4
+            }
+            ("ld", [Range(x, y), IndirectI]) => {
+                self.require_xo(name)?;
+                0x5003 | x << 8 | y << 4
+            }
+            ("ld", [Register(x), Value(kk)]) => 0x6000 | x << 8 | value(kk, byte)?,
+            ("add", [Register(x), Value(kk)]) => 0x7000 | x << 8 | value(kk, byte)?,
+            ("ld", [Register(x), Register(y)]) => 0x8000 | x << 8 | y << 4,
+            ("or", [Register(x), Register(y)]) => 0x8001 | x << 8 | y << 4,
+            ("and", [Register(x), Register(y)]) => 0x8002 | x << 8 | y << 4,
+            ("xor", [Register(x), Register(y)]) => 0x8003 | x << 8 | y << 4,
+            ("add", [Register(x), Register(y)]) => 0x8004 | x << 8 | y << 4,
+            ("sub", [Register(x), Register(y)]) => 0x8005 | x << 8 | y << 4,
+            ("shr", [Register(x), Register(y)]) => 0x8006 | x << 8 | y << 4,
+            ("shr", [Register(x)]) => 0x8006 | x << 8 | x << 4,
+            ("subn", [Register(x), Register(y)]) => 0x8007 | x << 8 | y << 4,
+            ("shl", [Register(x), Register(y)]) => 0x800e | x << 8 | y << 4,
+            ("shl", [Register(x)]) => 0x800e | x << 8 | x << 4,
+            ("ld", [I, Value(a)]) => 0xa000 | value(a, addr)?,
+            ("ld", [I, Long(a)]) => {
+                self.require_xo(name)?;
+                return Ok([0xf0, 0x00]
+                    .into_iter()
+                    .chain(value(a, word)?.to_be_bytes())
+                    .collect());
+            }
+            ("rnd", [Register(x), Value(kk)]) => 0xc000 | x << 8 | value(kk, byte)?,
+            ("drw", [Register(x), Register(y), Value(n)]) => {
+                let n = value(n, nibble)?;
+
+                if n == 0 {
+                    self.require_super(name)?;
+                }
+                0xd000 | x << 8 | y << 4 | n
+            }
+            ("skp", [Register(x)]) => 0xe09e | x << 8,
+            ("sknp", [Register(x)]) => 0xe0a1 | x << 8,
+            ("plane", [Value(n)]) => {
+                self.require_xo(name)?;
+                0xf001 | value(n, nibble)? << 8
+            }
+            ("audio", []) => {
+                self.require_xo(name)?;
+                0xf002
+            }
+            ("ld", [Register(x), Dt]) => 0xf007 | x << 8,
+            ("ld", [Register(x), K]) => 0xf00a | x << 8,
+            ("ld", [Dt, Register(x)]) => 0xf015 | x << 8,
+            ("ld", [St, Register(x)]) => 0xf018 | x << 8,
+            ("add", [I, Register(x)]) => 0xf01e | x << 8,
+            ("ld", [F, Register(x)]) => 0xf029 | x << 8,
+            ("ld", [Hf, Register(x)]) => {
+                self.require_super(name)?;
+                0xf030 | x << 8
+            }
+            ("ld", [B, Register(x)]) => 0xf033 | x << 8,
+            ("pitch", [Register(x)]) => {
+                self.require_xo(name)?;
+                0xf03a | x << 8
+            }
+            ("ld", [IndirectI, Register(x)]) => 0xf055 | x << 8,
+            ("ld", [Register(x), IndirectI]) => 0xf065 | x << 8,
+            ("ld", [R, Register(x)]) => {
+                self.require_super(name)?;
+                0xf075 | x << 8
+            }
+            ("ld", [Register(x), R]) => {
+                self.require_super(name)?;
+                0xf085 | x << 8
+            }
+            _ => return Err(format!("invalid instruction `{}`", name.to_uppercase())),
+        };
+
+        Ok(opcode.to_be_bytes().to_vec())
+    }
+
+    /// Assemble a source, the program starts at 0x200
+    pub fn assemble(&self, source: &str) -> Result<Vec<u8>, ChipError> {
+        let (statements, labels) = self.parse(source)?;
+        let mut program = Vec::new();
+
+        for statement in statements.iter() {
+            if statement.name == "org" {
+                if let [Operand::Value(Expr::Number(addr))] = statement.operands.as_slice() {
+                    program.resize((addr - PROGRAM_START as u32) as usize, 0);
+                }
+                continue;
+            }
+
+            let bytes = self
+                .encode(statement, &labels)
+                .map_err(|e| Self::error(statement.line, e))?;
+
+            program.extend(bytes);
+        }
+
+        Ok(program)
+    }
+}
@@ -0,0 +1,124 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    error::ChipError,
+    interpreters::{quirks::QuirksConfig, types::InterpreterType},
+    keymap::KeyMapConfig,
+    properties::color::Color,
+};
+
+/// Bundled database, JSON object with the ROM SHA-1 as key
+const BUNDLED: &str = include_str!("roms.json");
+
+/// Settings of a ROM, every value is optional
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RomSettings {
+    /// Program name
+    pub name: Option<String>,
+    /// Interpreter type
+    pub interpreter: Option<InterpreterType>,
+    /// Quirks preset and overrides
+    pub quirks: QuirksConfig,
+    /// Cycles per second (hz)
+    pub clock: Option<u64>,
+    /// Background, foreground, second plane and both planes colors
+    pub colors: Option<Vec<Color>>,
+    /// Keyboard layout and extra keys
+    pub keymap: KeyMapConfig,
+}
+
+impl RomSettings {
+    /// Merge `other` on top of `self`, its values win
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            name: other.name.or(self.name),
+            interpreter: other.interpreter.or(self.interpreter),
+            quirks: self.quirks.merge(other.quirks),
+            clock: other.clock.or(self.clock),
+            colors: other.colors.or(self.colors),
+            keymap: self.keymap.merge(other.keymap),
+        }
+    }
+}
+
+/// Per ROM settings, keyed by the SHA-1 of the program
+#[derive(Debug, Clone, Default)]
+pub struct RomDatabase {
+    entries: HashMap<String, RomSettings>,
+}
+
+impl RomDatabase {
+    /// Empty database
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Database shipped with the crate
+    pub fn bundled() -> Self {
+        Self::from_json(BUNDLED).expect("Invalid bundled ROM database")
+    }
+
+    /// Parse a JSON database
+    pub fn from_json(json: &str) -> Result<Self, ChipError> {
+        let entries: HashMap<String, RomSettings> =
+            serde_json::from_str(json).map_err(|e| ChipError::LoadDatabase(e.to_string()))?;
+
+        Ok(Self {
+            entries: entries
+                .into_iter()
+                .map(|(hash, settings)| (hash.to_lowercase(), settings))
+                .collect(),
+        })
+    }
+
+    /// Read a JSON database file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ChipError> {
+        let json = fs::read_to_string(path).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+
+        Self::from_json(&json)
+    }
+
+    /// Merge the entries of `other`, its values win
+    pub fn extend(&mut self, other: RomDatabase) {
+        for (hash, settings) in other.entries {
+            let merged = match self.entries.remove(&hash) {
+                Some(current) => current.merge(settings),
+                None => settings,
+            };
+
+            self.entries.insert(hash, merged);
+        }
+    }
+
+    /// Return the SHA-1 of a program, as lowercase hexadecimal
+    pub fn hash(program: &[u8]) -> String {
+        Sha1::digest(program)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Return the settings of a program
+    pub fn get(&self, program: &[u8]) -> Option<&RomSettings> {
+        self.get_hash(&Self::hash(program))
+    }
+
+    /// Return the settings of a program by its SHA-1
+    pub fn get_hash(&self, hash: &str) -> Option<&RomSettings> {
+        self.entries.get(&hash.to_lowercase())
+    }
+
+    /// Return the number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true if there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+};
+
+use crate::{
+    interpreters::pc::OPCODE_SIZE, models::interpreter::Interpreter, properties::opcode::Opcode,
+};
+
+/// Debugger prompt
+const PROMPT: &str = "(tinychip) ";
+
+/// Default number of bytes shown by `mem`
+const DUMP_SIZE: usize = 64;
+
+/// Default number of instructions shown by `disasm`
+const DISASM_SIZE: usize = 10;
+
+/// Commands help
+const HELP: &str = "\
+c, continue          resume the execution
+s, step [n]          execute n instruction(s), 1 by default
+b, break <addr>      set a breakpoint at addr
+w, watch <addr>      pause when the byte at addr changes
+d, delete <addr>     remove the breakpoint or watchpoint at addr
+i, info              list the breakpoints and watchpoints
+r, regs              show the registers
+st, stack            show the stack
+x, mem <addr> [n]    dump n bytes from addr, 64 by default
+l, disasm [addr] [n] disassemble n instructions from addr, pc by default
+q, quit              stop the emulator
+h, help              show this help
+
+Addresses are hexadecimal, an empty line repeats the last command.";
+
+/// What the emulator has to do when the debugger gives the hand back
+pub enum DebugAction {
+    /// Execute the next instruction
+    Resume,
+    /// Stop the emulator
+    Quit,
+}
+
+/// Interactive step debugger, controlled from the terminal
+#[derive(Default)]
+pub struct Debugger {
+    /// Execution paused, waiting for a command
+    paused: bool,
+    /// Instructions left before pausing again
+    steps: Option<usize>,
+    /// Program count breakpoints
+    breakpoints: BTreeSet<u16>,
+    /// Memory watchpoints, with the last known value
+    watchpoints: BTreeMap<u16, u8>,
+    /// Program count of the previous instruction
+    last_pc: Option<u16>,
+    /// Last command, repeated by an empty line
+    last_command: String,
+}
+
+/// Parse an hexadecimal address, the `0x` prefix is optional
+fn parse_addr(value: &str) -> Option<u16> {
+    let value = value.trim_start_matches("0x").trim_start_matches("0X");
+
+    u16::from_str_radix(value, 16).ok()
+}
+
+impl Debugger {
+    /// The debugger starts paused, before the first instruction
+    pub fn new() -> Self {
+        Self {
+            paused: true,
+            ..Self::default()
+        }
+    }
+
+    /// Pause before the next instruction
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Return true if the execution is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Called before every instruction, it blocks while the execution is paused
+    pub fn before_step(&mut self, interpreter: &dyn Interpreter) -> DebugAction {
+        let pc = interpreter.registers().pc;
+
+        // The program count does not move while waiting for a key
+        if self.last_pc != Some(pc) && self.breakpoints.contains(&pc) {
+            println!("Breakpoint at {:#05x}", pc);
+            self.paused = true;
+        }
+        self.last_pc = Some(pc);
+
+        if !self.paused {
+            return DebugAction::Resume;
+        }
+
+        self.print_disasm(interpreter, pc, 1);
+        self.prompt(interpreter)
+    }
+
+    /// Called after every instruction, it checks the watchpoints and the steps
+    pub fn after_step(&mut self, interpreter: &dyn Interpreter) {
+        for (addr, value) in self.watchpoints.iter_mut() {
+            let current = interpreter.read_byte(*addr as usize);
+
+            if current != *value {
+                println!(
+                    "Watchpoint {:#05x}: {:#04x} -> {:#04x}",
+                    addr, value, current
+                );
+                *value = current;
+                self.paused = true;
+            }
+        }
+
+        if let Some(steps) = self.steps {
+            if steps <= 1 {
+                self.steps = None;
+                self.paused = true;
+            } else {
+                self.steps = Some(steps - 1);
+            }
+        }
+    }
+
+    /// Read commands from stdin until one resumes the execution
+    fn prompt(&mut self, interpreter: &dyn Interpreter) -> DebugAction {
+        let stdin = io::stdin();
+
+        loop {
+            print!("{}", PROMPT);
+            io::stdout().flush().ok();
+
+            let mut line = String::new();
+
+            match stdin.read_line(&mut line) {
+                Ok(0) | Err(_) => return DebugAction::Quit,
+                Ok(_) => {}
+            }
+
+            let line = match line.trim() {
+                "" => self.last_command.clone(),
+                command => command.to_string(),
+            };
+
+            self.last_command = line.clone();
+
+            if let Some(action) = self.execute(&line, interpreter) {
+                return action;
+            }
+        }
+    }
+
+    /// Execute a command, return an action if it leaves the prompt
+    fn execute(&mut self, line: &str, interpreter: &dyn Interpreter) -> Option<DebugAction> {
+        let mut args = line.split_whitespace();
+        let command = args.next().unwrap_or_default();
+        let args: Vec<&str> = args.collect();
+        let addr = args.first().and_then(|value| parse_addr(value));
+        let count = |index: usize, default: usize| {
+            args.get(index)
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(default)
+        };
+
+        match (command, addr) {
+            ("" | "h" | "help", _) => println!("{}", HELP),
+            ("c" | "continue", _) => {
+                self.paused = false;
+                self.steps = None;
+
+                return Some(DebugAction::Resume);
+            }
+            ("s" | "step", _) => {
+                self.paused = false;
+                self.steps = Some(count(0, 1).max(1));
+
+                return Some(DebugAction::Resume);
+            }
+            ("q" | "quit", _) => return Some(DebugAction::Quit),
+            ("b" | "break", Some(addr)) => {
+                self.breakpoints.insert(addr);
+                println!("Breakpoint set at {:#05x}", addr);
+            }
+            ("w" | "watch", Some(addr)) => {
+                self.watchpoints
+                    .insert(addr, interpreter.read_byte(addr as usize));
+                println!("Watchpoint set at {:#05x}", addr);
+            }
+            ("d" | "delete", Some(addr)) => {
+                let removed =
+                    self.breakpoints.remove(&addr) | self.watchpoints.remove(&addr).is_some();
+
+                if !removed {
+                    println!("Nothing at {:#05x}", addr);
+                }
+            }
+            ("i" | "info", _) => self.print_info(),
+            ("r" | "regs", _) => Self::print_registers(interpreter),
+            ("st" | "stack", _) => Self::print_stack(interpreter),
+            ("x" | "mem", Some(addr)) => Self::print_memory(interpreter, addr, count(1, DUMP_SIZE)),
+            ("l" | "disasm", _) => {
+                let addr = addr.unwrap_or(interpreter.registers().pc);
+
+                self.print_disasm(interpreter, addr, count(1, DISASM_SIZE));
+            }
+            ("b" | "break" | "w" | "watch" | "d" | "delete" | "x" | "mem", None) => {
+                println!("Missing or invalid address")
+            }
+            (command, _) => println!("Unknown command `{}`, try `help`", command),
+        }
+
+        None
+    }
+
+    /// List the breakpoints and the watchpoints
+    fn print_info(&self) {
+        for addr in self.breakpoints.iter() {
+            println!("Breakpoint {:#05x}", addr);
+        }
+        for (addr, value) in self.watchpoints.iter() {
+            println!("Watchpoint {:#05x} = {:#04x}", addr, value);
+        }
+    }
+
+    /// Show the registers
+    fn print_registers(interpreter: &dyn Interpreter) {
+        let registers = interpreter.registers();
+
+        for (row, values) in registers.v.chunks(8).enumerate() {
+            let line: Vec<String> = values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| format!("V{:X}={:02x}", row * 8 + index, value))
+                .collect();
+
+            println!("{}", line.join(" "));
+        }
+        println!(
+            "I={:04x} PC={:04x} SP={:x} DT={:02x} ST={:02x}",
+            registers.i, registers.pc, registers.sp, registers.delay_timer, registers.sound_timer
+        );
+    }
+
+    /// Show the stack, the last call first
+    fn print_stack(interpreter: &dyn Interpreter) {
+        let registers = interpreter.registers();
+        let depth = (registers.sp as usize).min(registers.stack.len());
+
+        if depth == 0 {
+            println!("Empty stack");
+        }
+        for index in (0..depth).rev() {
+            println!("#{:<2} {:#05x}", index, registers.stack[index]);
+        }
+    }
+
+    /// Dump `size` bytes of memory from `addr`
+    fn print_memory(interpreter: &dyn Interpreter, addr: u16, size: usize) {
+        for row in (0..size).step_by(16) {
+            let start = addr as usize + row;
+            let bytes: Vec<String> = (start..start + 16.min(size - row))
+                .map(|index| format!("{:02x}", interpreter.read_byte(index)))
+                .collect();
+
+            println!("{:04x}  {}", start, bytes.join(" "));
+        }
+    }
+
+    /// Disassemble `count` instructions from `addr`
+    fn print_disasm(&self, interpreter: &dyn Interpreter, addr: u16, count: usize) {
+        let kind = interpreter.kind();
+        let pc = interpreter.registers().pc;
+        let mut addr = addr;
+
+        for _ in 0..count {
+            let opcode = Opcode::from(interpreter.read_short(addr as usize));
+            let next = interpreter.read_short(addr.wrapping_add(OPCODE_SIZE) as usize);
+            let marker = match (addr == pc, self.breakpoints.contains(&addr)) {
+                (true, _) => "=>",
+                (false, true) => " *",
+                (false, false) => "  ",
+            };
+
+            println!(
+                "{} {:04x}  {:04x}  {}",
+                marker,
+                addr,
+                opcode.value,
+                opcode.mnemonic(kind, next)
+            );
+
+            let size = if opcode.is_long(kind) {
+                OPCODE_SIZE * 2
+            } else {
+                OPCODE_SIZE
+            };
+
+            addr = addr.wrapping_add(size);
+        }
+    }
+}
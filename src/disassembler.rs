@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    interpreters::{
+        pc::{OPCODE_SIZE, PROGRAM_START},
+        types::InterpreterType,
+    },
+    properties::opcode::{Mnemonic, Opcode},
+};
+
+/// Column of the listing comments
+const COMMENT_COLUMN: usize = 28;
+
+/// A disassembled instruction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    /// Instruction address
+    pub addr: u16,
+    /// Raw bytes (2, 4 for `F000 nnnn` or 1 for a trailing byte)
+    pub bytes: Vec<u8>,
+    /// Assembly form, the jump and call targets are replaced by their label
+    pub mnemonic: Mnemonic,
+    /// Label, if the instruction is a jump or call target
+    pub label: Option<String>,
+}
+
+impl Display for Line {
+    /// Assembly source form, the address and the raw bytes are in a comment
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes: Vec<String> = self.bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+        if let Some(label) = &self.label {
+            writeln!(f, "{}:", label)?;
+        }
+
+        write!(
+            f,
+            "    {:<width$}; {:04x}: {}",
+            self.mnemonic.to_string(),
+            self.addr,
+            bytes.join(" "),
+            width = COMMENT_COLUMN - 4
+        )
+    }
+}
+
+/// Kind of branch target
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    /// `JP addr`, `JP V0, addr`
+    Jump,
+    /// `CALL addr`
+    Call,
+}
+
+/// Turns a program into annotated mnemonics, with a linear sweep
+///
+/// Data inside the program is decoded like instructions, as on the real machine
+/// there is no way to tell them apart.
+pub struct Disassembler {
+    /// Interpreter type, for the SUPER-CHIP and XO-CHIP instructions
+    kind: InterpreterType,
+    /// Program load address
+    origin: u16,
+}
+
+impl Disassembler {
+    pub fn new(kind: InterpreterType) -> Self {
+        Self {
+            kind,
+            origin: PROGRAM_START,
+        }
+    }
+
+    /// Set the program load address
+    pub fn set_origin(mut self, origin: u16) -> Self {
+        self.origin = origin;
+
+        self
+    }
+
+    /// Return the label name of a branch target
+    fn label(target: Target, addr: u16) -> String {
+        match target {
+            Target::Jump => format!("label_{:03x}", addr),
+            Target::Call => format!("sub_{:03x}", addr),
+        }
+    }
+
+    /// Return the branch target of an opcode
+    fn target(opcode: Opcode) -> Option<(Target, u16)> {
+        match opcode.value >> 12 {
+            0x1 | 0xb => Some((Target::Jump, opcode.nnn())),
+            0x2 => Some((Target::Call, opcode.nnn())),
+            _ => None,
+        }
+    }
+
+    /// Disassemble a program
+    pub fn disassemble(&self, program: &[u8]) -> Vec<Line> {
+        let word = |index: usize| -> u16 {
+            let high = *program.get(index).unwrap_or(&0) as u16;
+            let low = *program.get(index + 1).unwrap_or(&0) as u16;
+
+            high << 8 | low
+        };
+
+        // First pass, decoding
+        let mut decoded = Vec::new();
+        let mut index = 0;
+
+        while index < program.len() {
+            let addr = self.origin.wrapping_add(index as u16);
+
+            // Trailing odd byte
+            if index + 1 == program.len() {
+                let mnemonic = Mnemonic::new("DB", format!("{:#04x}", program[index]));
+
+                decoded.push((addr, vec![program[index]], None, mnemonic));
+                break;
+            }
+
+            let opcode = Opcode::from(word(index));
+            let size = if opcode.is_long(self.kind) && index + 3 < program.len() {
+                OPCODE_SIZE * 2
+            } else {
+                OPCODE_SIZE
+            } as usize;
+            let mnemonic = opcode.mnemonic(self.kind, word(index + 2));
+
+            decoded.push((
+                addr,
+                program[index..index + size].to_vec(),
+                Some(opcode),
+                mnemonic,
+            ));
+            index += size;
+        }
+
+        // Second pass, labels on the targets that are instructions of the program
+        let mut labels = BTreeMap::new();
+
+        for (_, _, opcode, _) in decoded.iter() {
+            if let Some((target, addr)) = opcode.and_then(Self::target) {
+                if decoded.iter().any(|(start, ..)| *start == addr) {
+                    // A call label wins over a jump label
+                    let entry = labels.entry(addr).or_insert(target);
+
+                    if target == Target::Call {
+                        *entry = target;
+                    }
+                }
+            }
+        }
+
+        decoded
+            .into_iter()
+            .map(|(addr, bytes, opcode, mut mnemonic)| {
+                if let Some((_, target)) = opcode.and_then(Self::target) {
+                    if let Some(kind) = labels.get(&target) {
+                        let address = format!("{:#05x}", target);
+
+                        mnemonic.operands = mnemonic
+                            .operands
+                            .replace(&address, &Self::label(*kind, target));
+                    }
+                }
+
+                Line {
+                    addr,
+                    bytes,
+                    mnemonic,
+                    label: labels.get(&addr).map(|kind| Self::label(*kind, addr)),
+                }
+            })
+            .collect()
+    }
+
+    /// Disassemble a program into an assembly source
+    pub fn listing(&self, program: &[u8]) -> String {
+        let mut ret = String::new();
+
+        if self.origin != PROGRAM_START {
+            ret.push_str(&format!("    org {:#05x}\n", self.origin));
+        }
+
+        for line in self.disassemble(program) {
+            ret.push_str(&line.to_string());
+            ret.push('\n');
+        }
+
+        ret
+    }
+}
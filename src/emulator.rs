@@ -1,21 +1,62 @@
-use std::{fs::File, io::Read, path::Path, thread, time};
-
-use crate::apis::api::{ApiKind, GraphicProp, WINDOW_MIN_H, WINDOW_MIN_W};
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+    thread, time,
+};
+
+use crate::apis::api::{
+    ApiKind, GraphicProp, WINDOW_MAX_H, WINDOW_MAX_W, WINDOW_MIN_H, WINDOW_MIN_W,
+};
+use crate::apis::libs::headless::{HeadlessApi, InputScript};
+use crate::database::{RomDatabase, RomSettings};
+use crate::debugger::{DebugAction, Debugger};
 use crate::error::ChipError;
+use crate::event::{Hotkey, Input, KeyEvent};
 use crate::interpreters::interpreter::ChipInterpreter;
+use crate::keymap::{KeyMap, KeyMapConfig};
 use crate::models::{api::Api, core::Core, interpreter::Interpreter};
-use crate::properties::{color::ColorPreset, rectangle::Rectangle};
+use crate::movie::{Movie, MovieHeader};
+use crate::properties::{
+    color::Color, palette::Palette, pattern::AudioPattern, phosphor::Phosphor,
+    rectangle::Rectangle, scaling::ScaleMode,
+};
+use crate::screenshot::save_screenshot;
+use crate::status::{Status, StatusCallback};
+use crate::video::VideoRecorder;
+
+/// Number of quick save slots
+const STATE_SLOTS: u8 = 10;
+
+/// How long a status stays in the window title
+const STATUS_DURATION: time::Duration = time::Duration::from_secs(3);
+
+/// Frames per second, the timers are decremented once per frame
+pub const FRAME_RATE: u64 = 60;
 
 impl Default for EmulatorBuilder {
     fn default() -> Self {
         Self {
             api_prop: GraphicProp {
-                api: ApiKind::Sdl,
+                api: ApiKind::default(),
                 title: String::from("chip8"),
                 size: (WINDOW_MIN_W, WINDOW_MIN_H),
+                headless: Default::default(),
             },
             interpreter: Box::new(ChipInterpreter::new()),
             clock: 500,
+            debug: false,
+            fullscreen: false,
+            seed: None,
+            palette: Palette::default(),
+            phosphor: None,
+            scale_mode: ScaleMode::default(),
+            record: None,
+            replay: None,
+            video: None,
+            database: RomDatabase::bundled(),
+            settings: RomSettings::default(),
+            on_status: None,
         }
     }
 }
@@ -25,6 +66,18 @@ pub struct EmulatorBuilder {
     api_prop: GraphicProp,
     interpreter: Box<dyn Interpreter>,
     clock: u64,
+    debug: bool,
+    fullscreen: bool,
+    seed: Option<u64>,
+    palette: Palette,
+    phosphor: Option<Phosphor>,
+    scale_mode: ScaleMode,
+    record: Option<PathBuf>,
+    replay: Option<Movie>,
+    video: Option<PathBuf>,
+    database: RomDatabase,
+    settings: RomSettings,
+    on_status: Option<StatusCallback>,
 }
 
 impl EmulatorBuilder {
@@ -46,9 +99,40 @@ impl EmulatorBuilder {
         self
     }
 
-    /// Set the window size
+    /// Set the window size, kept between `WINDOW_MIN_*` and `WINDOW_MAX_*`
     pub fn set_window_size(mut self, size: (u32, u32)) -> Self {
-        self.api_prop.size = size;
+        self.api_prop.size = (
+            size.0.clamp(WINDOW_MIN_W, WINDOW_MAX_W),
+            size.1.clamp(WINDOW_MIN_H, WINDOW_MAX_H),
+        );
+
+        self
+    }
+
+    /// Start in the fullscreen mode
+    pub fn set_fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+
+        self
+    }
+
+    /// Set the maximum number of frames (headless only)
+    pub fn set_max_frames(mut self, frames: u64) -> Self {
+        self.api_prop.headless.max_frames = Some(frames);
+
+        self
+    }
+
+    /// Set the maximum run duration (headless only)
+    pub fn set_timeout(mut self, timeout: time::Duration) -> Self {
+        self.api_prop.headless.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Set the scripted inputs (headless only)
+    pub fn set_input_script(mut self, script: InputScript) -> Self {
+        self.api_prop.headless.script = script;
 
         self
     }
@@ -67,13 +151,130 @@ impl EmulatorBuilder {
         self
     }
 
+    /// Set the ROM database, used by `Emulator::load_from_file`
+    pub fn set_database(mut self, database: RomDatabase) -> Self {
+        self.database = database;
+
+        self
+    }
+
+    /// Set the settings, they win over the ROM database ones
+    pub fn set_settings(mut self, settings: RomSettings) -> Self {
+        self.settings = settings;
+
+        self
+    }
+
+    /// Set the keyboard mapping, it is the keymap of the settings
+    ///
+    /// The keys of a ROM database entry are added on top of it
+    pub fn set_keymap(mut self, keymap: KeyMapConfig) -> Self {
+        self.settings.keymap = keymap;
+
+        self
+    }
+
+    /// Enable the interactive debugger
+    pub fn set_debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+
+        self
+    }
+
+    /// Set the pixel colors, the ones of the settings are applied on top of them
+    pub fn set_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+
+        self
+    }
+
+    /// Enable the phosphor persistence filter, `decay` is the part of the
+    /// previous color kept at every frame (0 to 1)
+    pub fn set_phosphor(mut self, decay: f32) -> Self {
+        self.phosphor = Some(Phosphor::new(decay));
+
+        self
+    }
+
+    /// Set how the screen is scaled to the window
+    pub fn set_scale_mode(mut self, mode: ScaleMode) -> Self {
+        self.scale_mode = mode;
+
+        self
+    }
+
+    /// Enable the deterministic mode, with a seeded random numbers generator
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+
+        self
+    }
+
+    /// Record the inputs into a movie file, written when the run ends
+    pub fn set_record<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.record = Some(path.as_ref().to_path_buf());
+
+        self
+    }
+
+    /// Replay the inputs of a movie, its header wins over the other settings
+    pub fn set_replay(mut self, movie: Movie) -> Self {
+        self.replay = Some(movie);
+
+        self
+    }
+
+    /// Record the displayed screens into a GIF file, from the start of the run
+    pub fn set_video_record<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.video = Some(path.as_ref().to_path_buf());
+
+        self
+    }
+
+    /// Set the function called with every status, besides the window title
+    pub fn set_status_callback<F: FnMut(&Status) + 'static>(mut self, callback: F) -> Self {
+        self.on_status = Some(Box::new(callback));
+
+        self
+    }
+
     /// Build the emulator
     pub fn build(self) -> Emulator {
-        Emulator {
+        let settings = self.settings.clone();
+        let window_title = self.api_prop.title.clone();
+        let mut emulator = Emulator {
             api: self.api_prop.into(),
             interpreter: self.interpreter,
             clock: self.clock,
+            pattern: None,
+            rom_path: None,
+            events: Vec::new(),
+            slot: 0,
+            debugger: self.debug.then(Debugger::new),
+            seed: self.seed,
+            record: self.record,
+            movie: self.replay,
+            frame: 0,
+            video_path: self.video,
+            video: None,
+            database: self.database,
+            settings: self.settings,
+            palette: self.palette,
+            phosphor: self.phosphor,
+            scale_mode: self.scale_mode,
+            window_title,
+            title: String::new(),
+            fullscreen: false,
+            paused: false,
+            status: None,
+            on_status: self.on_status,
+        };
+
+        emulator.apply_settings(&settings);
+        if self.fullscreen {
+            emulator.set_fullscreen(true);
         }
+        emulator
     }
 }
 
@@ -85,17 +286,206 @@ pub struct Emulator {
     api: Box<dyn Api>,
     /// Cycles per second (hz)
     pub clock: u64,
+    /// Last audio pattern given to the API
+    pattern: Option<AudioPattern>,
+    /// Loaded program path
+    rom_path: Option<PathBuf>,
+    /// Input edges kept during a pause, given to the interpreter when resuming
+    events: Vec<KeyEvent<Input>>,
+    /// Quick save slot
+    slot: u8,
+    /// Interactive debugger
+    debugger: Option<Debugger>,
+    /// Seed of the deterministic mode
+    seed: Option<u64>,
+    /// Movie file to record
+    record: Option<PathBuf>,
+    /// Movie being recorded or replayed
+    movie: Option<Movie>,
+    /// Frames since the program has been loaded
+    frame: u64,
+    /// Video file to record at the start of the run
+    video_path: Option<PathBuf>,
+    /// Video being recorded
+    video: Option<VideoRecorder>,
+    /// Per ROM settings
+    database: RomDatabase,
+    /// Settings that win over the ROM database
+    settings: RomSettings,
+    /// Pixel colors
+    palette: Palette,
+    /// Display filter
+    phosphor: Option<Phosphor>,
+    /// How the screen is scaled to the window
+    scale_mode: ScaleMode,
+    /// Window title given to the builder
+    window_title: String,
+    /// Window title currently displayed, with the program name and state
+    title: String,
+    /// Fullscreen mode
+    fullscreen: bool,
+    /// Emulation paused, the events are still handled
+    paused: bool,
+    /// Last status and until when it is shown in the window title
+    status: Option<(Status, time::Instant)>,
+    /// Called with every status
+    on_status: Option<StatusCallback>,
 }
 
 impl Emulator {
+    /// Apply the settings, the interpreter is replaced if its type differs
+    ///
+    /// The quirks and the keymap of the replaced interpreter are kept, unless
+    /// the settings have their own.
+    pub fn apply_settings(&mut self, settings: &RomSettings) {
+        if let Some(kind) = settings.interpreter {
+            if kind != self.interpreter.kind() {
+                let quirks = self.interpreter.quirks();
+                let keymap = self.interpreter.keymap();
+
+                self.interpreter = kind.into();
+                self.interpreter.set_quirks(quirks);
+                self.interpreter.set_keymap(keymap);
+            }
+        }
+        if !settings.quirks.is_empty() {
+            self.interpreter.set_quirks(settings.quirks.quirks());
+        }
+        if !settings.keymap.is_empty() {
+            self.interpreter.set_keymap(KeyMap::from(&settings.keymap));
+        }
+        if let Some(clock) = settings.clock {
+            self.clock = clock;
+        }
+        if let Some(colors) = &settings.colors {
+            self.palette = self.palette.with_colors(colors);
+        }
+    }
+
     /// Load program raw bytes
-    pub fn load<T: Into<Vec<u8>>>(&mut self, program: T) {
-        self.interpreter.load_program(program.into());
+    ///
+    /// In the deterministic mode, the random numbers generator is seeded again.
+    /// A replayed movie must have been recorded with the same program.
+    pub fn load<T: Into<Vec<u8>>>(&mut self, program: T) -> Result<(), ChipError> {
+        let program = program.into();
+
+        self.start_movie(&program)?;
+        if let Some(seed) = self.seed {
+            self.interpreter.set_seed(seed);
+        }
+        self.interpreter.load_program(program)
+    }
+
+    /// Start the movie recording, or apply the header of the replayed movie
+    ///
+    /// A recording always runs in the deterministic mode.
+    fn start_movie(&mut self, program: &[u8]) -> Result<(), ChipError> {
+        self.frame = 0;
+
+        if self.record.is_some() {
+            let seed = *self.seed.get_or_insert_with(rand::random);
+            let header = MovieHeader {
+                rom: RomDatabase::hash(program),
+                interpreter: self.interpreter.kind(),
+                seed,
+                clock: self.clock,
+                quirks: self.interpreter.quirks(),
+                keymap: self.interpreter.keymap(),
+                frames: 0,
+            };
+
+            self.movie = Some(Movie::new(header));
+        } else if let Some(movie) = &self.movie {
+            let header = movie.header.clone();
+            let rom = RomDatabase::hash(program);
+
+            if header.rom != rom {
+                return Err(ChipError::LoadMovie(format!(
+                    "recorded with the ROM {}, not {}",
+                    header.rom, rom
+                )));
+            }
+            if header.interpreter != self.interpreter.kind() {
+                self.interpreter = header.interpreter.into();
+            }
+            self.interpreter.set_quirks(header.quirks);
+            self.interpreter.set_keymap(header.keymap);
+            self.clock = header.clock;
+            self.seed = Some(header.seed);
+        }
+
+        Ok(())
+    }
+
+    /// Record the input edges of the current frame, or replace them by the replayed ones
+    ///
+    /// After the end of a replay, the replayed inputs are released and the live
+    /// inputs are used again.
+    fn movie_events(&mut self, events: Vec<KeyEvent<Input>>) -> Vec<KeyEvent<Input>> {
+        let frame = self.frame;
+
+        self.frame += 1;
+
+        match self.movie.as_mut() {
+            Some(movie) if self.record.is_some() => {
+                movie.push(frame, &events);
+
+                events
+            }
+            Some(movie) => match movie.events(frame) {
+                Some(replayed) => replayed,
+                None if frame == movie.header.frames => {
+                    let mut released: Vec<KeyEvent<Input>> =
+                        movie.held().into_iter().map(KeyEvent::Up).collect();
+
+                    released.extend(events);
+                    self.report(Status::ReplayEnded(frame));
+
+                    released
+                }
+                None => events,
+            },
+            None => events,
+        }
+    }
+
+    /// Write the recorded movie
+    fn save_movie(&mut self) {
+        if let (Some(path), Some(movie)) = (&self.record, &self.movie) {
+            let status = match movie.save(path) {
+                Ok(()) => Status::MovieRecorded(path.clone()),
+                Err(e) => Status::Error(e.to_string()),
+            };
+
+            self.report(status);
+        }
+    }
+
+    /// Show a status in the window title for a while, and give it to the callback
+    fn report(&mut self, status: Status) {
+        if let Some(callback) = self.on_status.as_mut() {
+            callback(&status);
+        }
+        self.status = Some((status, time::Instant::now() + STATUS_DURATION));
+    }
+
+    /// Report an error as a status, the emulation goes on
+    fn report_error(&mut self, result: Result<(), ChipError>) {
+        if let Err(e) = result {
+            self.report(Status::Error(e.to_string()));
+        }
+    }
+
+    /// Return the seed of the deterministic mode
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
     }
 
     /// Load a program from file
+    ///
+    /// The settings found in the ROM database are applied first
     pub fn load_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ChipError> {
-        let f = File::open(path);
+        let f = File::open(path.as_ref());
 
         match f {
             Ok(mut file) => {
@@ -103,7 +493,16 @@ impl Emulator {
 
                 match file.read_to_end(data.as_mut()) {
                     Ok(_) => {
-                        self.load(data);
+                        if let Some(settings) = self.database.get(&data) {
+                            let settings = settings.clone().merge(self.settings.clone());
+
+                            if let Some(name) = &settings.name {
+                                self.report(Status::DatabaseSettings(name.clone()));
+                            }
+                            self.apply_settings(&settings);
+                        }
+                        self.load(data)?;
+                        self.rom_path = Some(path.as_ref().to_path_buf());
 
                         Ok(())
                     }
@@ -113,61 +512,407 @@ impl Emulator {
             Err(e) => Err(ChipError::ReadFile(e.to_string())),
         }
     }
+
+    /// Save the interpreter state into a file
+    pub fn save_state_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ChipError> {
+        let state = self.interpreter.save_state();
+
+        fs::write(path, state).map_err(|e| ChipError::WriteFile(e.to_string()))
+    }
+
+    /// Restore the interpreter state from a file
+    pub fn load_state_from_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ChipError> {
+        let state = fs::read(path).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+
+        self.interpreter.load_state(&state)?;
+
+        // The screen may be completely different
+        self.draw_vram()?;
+        self.api.display();
+
+        Ok(())
+    }
+
+    /// Return the quick save file path for `slot`, next to the program
+    pub fn state_path(&self, slot: u8) -> PathBuf {
+        let mut path = self
+            .rom_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("tinychip"))
+            .into_os_string();
+
+        path.push(format!(".state{}", slot));
+        path.into()
+    }
+
+    /// Save the screen into a PNG or PPM file, every pixel is a `scale` x `scale` square
+    pub fn screenshot<P: AsRef<Path>>(&self, path: P, scale: usize) -> Result<(), ChipError> {
+        save_screenshot(self.interpreter.vram(), &self.palette, scale, path)
+    }
+
+    /// Return the first unused `<program>.<name><index>.<extension>` file path
+    fn unused_path(&self, name: &str, extension: &str) -> PathBuf {
+        let rom = self
+            .rom_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("tinychip"))
+            .into_os_string();
+
+        (0..)
+            .map(|index| {
+                let mut path = rom.clone();
+
+                path.push(format!(".{}{}.{}", name, index, extension));
+                PathBuf::from(path)
+            })
+            .find(|path| !path.exists())
+            .unwrap()
+    }
+
+    /// Return the first unused screenshot file path, next to the program
+    pub fn screenshot_path(&self) -> PathBuf {
+        self.unused_path("screenshot", "png")
+    }
+
+    /// Return the headless backend, to read its framebuffer, frames and beep state
+    pub fn headless(&self) -> Option<&HeadlessApi> {
+        self.api.as_any().downcast_ref()
+    }
+
+    /// Switch between the fullscreen and windowed modes
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.fullscreen = fullscreen;
+        self.api.set_fullscreen(fullscreen);
+    }
+
+    /// Return true in the fullscreen mode
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Pause or resume the emulation
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Return true if the emulation is paused, by the hotkey or the debugger
+    pub fn is_paused(&self) -> bool {
+        self.paused || self.debugger.as_ref().is_some_and(Debugger::is_paused)
+    }
+
+    /// Return the window title, with the program name, the clock and the pause state
+    pub fn title(&self) -> String {
+        let mut title = self.window_title.clone();
+
+        if let Some(name) = self.rom_path.as_ref().and_then(|path| path.file_name()) {
+            title += &format!(" - {}", name.to_string_lossy());
+        }
+        title += &format!(" - {} Hz", self.clock);
+
+        if self.is_paused() {
+            title += " - paused";
+        }
+        if let Some((status, until)) = &self.status {
+            if time::Instant::now() < *until {
+                title += &format!(" - {}", status);
+            }
+        }
+
+        title
+    }
+
+    /// Update the window title if it has changed
+    fn update_title(&mut self) {
+        let title = self.title();
+
+        if title != self.title {
+            self.api.set_title(&title);
+            self.title = title;
+        }
+    }
+
+    /// Resize the window to the next integer scale of the screen, within the window limits
+    ///
+    /// After the largest scale, it goes back to the smallest one.
+    fn cycle_window_scale(&mut self) {
+        if self.fullscreen {
+            return;
+        }
+
+        let vram = self.interpreter.vram();
+        let (w, h) = (vram.w() as u32, vram.h() as u32);
+        let (window_w, window_h) = self.api.window_size();
+
+        let min = WINDOW_MIN_W.div_ceil(w).max(WINDOW_MIN_H.div_ceil(h));
+        let max = (WINDOW_MAX_W / w).min(WINDOW_MAX_H / h);
+        let scale = match (window_w / w).min(window_h / h) + 1 {
+            scale if scale > max => min,
+            scale => scale.max(min),
+        };
+
+        self.api.set_window_size((w * scale, h * scale));
+        self.report(Status::WindowScale(scale));
+    }
+
+    /// Return the window area covered by the screen
+    fn screen_area(&self) -> Rectangle {
+        let vram = self.interpreter.vram();
+
+        self.scale_mode
+            .area(self.api.window_size(), (vram.w(), vram.h()))
+    }
+
+    /// Return the first unused video file path, next to the program
+    pub fn video_path(&self) -> PathBuf {
+        self.unused_path("video", "gif")
+    }
+
+    /// Start recording the displayed screens into a GIF file, at the displayed size
+    pub fn start_video<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ChipError> {
+        let vram = self.interpreter.vram();
+        let area = self.screen_area();
+        let size = (area.w as usize, area.h as usize);
+        let mut video = VideoRecorder::new(path, size, &self.palette, self.frame)?;
+
+        video.capture(self.frame, vram)?;
+        self.video = Some(video);
+
+        Ok(())
+    }
+
+    /// Stop the video recording, if any
+    pub fn stop_video(&mut self) -> Result<(), ChipError> {
+        match self.video.take() {
+            Some(video) => video.finish(self.frame),
+            None => Ok(()),
+        }
+    }
+
+    /// Start or stop the video recording
+    fn toggle_video(&mut self) -> Result<(), ChipError> {
+        if self.video.is_some() {
+            self.stop_video()?;
+            self.report(Status::VideoStopped);
+        } else {
+            let path = self.video_path();
+
+            self.start_video(&path)?;
+            self.report(Status::VideoStarted(path));
+        }
+
+        Ok(())
+    }
+
+    /// Save the screen at the window scale into the next screenshot file
+    fn take_screenshot(&mut self) -> Result<(), ChipError> {
+        let vram = self.interpreter.vram();
+        let (w, h) = self.api.window_size();
+        let scale = (w as usize / vram.w()).min(h as usize / vram.h());
+        let path = self.screenshot_path();
+
+        save_screenshot(vram, &self.palette, scale, &path)?;
+        self.report(Status::ScreenshotSaved(path));
+
+        Ok(())
+    }
+
+    /// Handle the emulator hotkeys
+    ///
+    /// - F3 : next integer window scale
+    /// - F4 / Pause : pause or resume the emulation
+    /// - F5 : quick save
+    /// - F6 : select the next quick save slot
+    /// - F7 : pause into the debugger
+    /// - F8 : start or stop the video recording
+    /// - F9 : quick load
+    /// - F11 : fullscreen or windowed mode
+    /// - F12 : screenshot
+    fn handle_hotkeys(&mut self, events: &[KeyEvent<Input>]) {
+        let pressed = events.iter().filter_map(|event| match event {
+            KeyEvent::Down(Input::Hotkey(key)) => Some(*key),
+            _ => None,
+        });
+
+        for key in pressed.collect::<Vec<Hotkey>>() {
+            let result = match key {
+                Hotkey::F3 => {
+                    self.cycle_window_scale();
+
+                    Ok(())
+                }
+                Hotkey::F4 | Hotkey::Pause => {
+                    self.set_paused(!self.paused);
+
+                    Ok(())
+                }
+                Hotkey::F5 => self.save_state_to_file(self.state_path(self.slot)),
+                Hotkey::F6 => {
+                    self.slot = (self.slot + 1) % STATE_SLOTS;
+                    self.report(Status::SaveSlot(self.slot));
+
+                    Ok(())
+                }
+                Hotkey::F7 => {
+                    if let Some(debugger) = self.debugger.as_mut() {
+                        debugger.pause();
+                    }
+
+                    Ok(())
+                }
+                Hotkey::F8 => self.toggle_video(),
+                Hotkey::F9 => self.load_state_from_file(self.state_path(self.slot)),
+                Hotkey::F11 => {
+                    self.set_fullscreen(!self.fullscreen);
+
+                    Ok(())
+                }
+                Hotkey::F12 => self.take_screenshot(),
+                _ => Ok(()),
+            };
+
+            self.report_error(result);
+        }
+    }
 }
 
 impl Core for Emulator {
     fn run(&mut self) {
-        let dur = time::Duration::from_micros(1_000_000 / self.clock);
+        let frame = time::Duration::from_micros(1_000_000 / FRAME_RATE);
+        let mut deadline = time::Instant::now();
         let mut win_size = self.api.window_size();
+        // Cycles left over by the previous frames, as the clock is rarely a multiple of 60
+        let mut budget = 0;
+
+        if let Some(path) = self.video_path.take() {
+            let result = self.start_video(path);
+
+            self.report_error(result);
+        }
+
+        'frames: while self.api.is_window_open() && !self.interpreter.exited() {
+            // Handling events + get the keyboard / mouse edges, once per frame
+            let events = self.api.events();
+
+            self.handle_hotkeys(&events);
+            self.update_title();
+            self.events.extend(events);
+
+            // The interpreter calls the instructions of the frame
+            let mut display = false;
+
+            // A paused frame is not emulated, nor recorded into the movie
+            if !self.paused {
+                let events = std::mem::take(&mut self.events);
+                let events = self.movie_events(events);
+
+                budget += self.clock;
+                let cycles = budget / FRAME_RATE;
+                budget %= FRAME_RATE;
 
-        while self.api.is_window_open() == true {
-            // Handling events + get keyboard / mouse inputs
-            let inputs = self.api.events();
+                for cycle in 0..cycles {
+                    // The title shows the pause before waiting for the commands
+                    if self.debugger.as_ref().is_some_and(Debugger::is_paused) {
+                        self.update_title();
+                    }
+                    if let Some(debugger) = self.debugger.as_mut() {
+                        if let DebugAction::Quit = debugger.before_step(self.interpreter.as_ref()) {
+                            break 'frames;
+                        }
+                    }
+
+                    // The edges of the frame are given once
+                    let events = match cycle {
+                        0 => events.as_slice(),
+                        _ => &[],
+                    };
+
+                    display |= self.interpreter.step(events);
+
+                    if let Some(debugger) = self.debugger.as_mut() {
+                        debugger.after_step(self.interpreter.as_ref());
+                    }
+                    if self.interpreter.exited() {
+                        break;
+                    }
+                }
+
+                self.interpreter.timers_tick();
+            }
 
-            // The interpreter calls the current instruction
-            let display = self.interpreter.step(inputs);
             let size_changed = self.api.window_size() != win_size;
 
-            if display == true || size_changed == true {
-                self.draw_vram();
+            // The phosphor filter fades the pixels at every frame
+            if display == true || size_changed == true || self.phosphor.is_some() {
+                if let Err(e) = self.draw_vram() {
+                    self.report(Status::Error(e.to_string()));
+                    break 'frames;
+                }
 
                 if size_changed {
                     win_size = self.api.window_size();
                 }
                 self.api.display();
+
+                if let Some(video) = self.video.as_mut() {
+                    let result = video.capture(self.frame, self.interpreter.vram());
+
+                    self.report_error(result);
+                }
             }
 
-            self.try_beep();
+            if self.paused {
+                self.api.pause_beep();
+            } else {
+                self.try_beep();
+            }
+
+            // Wait for the next frame, without catching up after a long pause (debugger)
+            deadline += frame;
+            let now = time::Instant::now();
 
-            thread::sleep(dur);
+            if deadline > now {
+                thread::sleep(deadline - now);
+            } else {
+                deadline = now;
+            }
         }
+
+        self.save_movie();
+
+        let result = self.stop_video();
+
+        self.report_error(result);
     }
 
     /// Draw the vram throught the graphical API
-    fn draw_vram(&mut self) {
+    fn draw_vram(&mut self) -> Result<(), ChipError> {
+        let area = self.screen_area();
         let vram = self.interpreter.vram();
-        let wsize = self.api.window_size();
-        let (w, h) = (wsize.0 as usize / vram.w(), wsize.1 as usize / vram.h());
-
-        for (i, value) in vram.value().iter().enumerate() {
-            // Rectangle size
-            let x = ((i % vram.w()) * w) as i32;
-            let y = ((i / vram.w()) * h) as i32;
-
-            // Rectangle properties
-            let rect = Rectangle::from((x, y, w as u32, h as u32));
-            let color = if value & 1 == 1 {
-                ColorPreset::White.into()
-            } else {
-                ColorPreset::Black.into()
-            };
 
-            // Draw the rectangle
-            self.api.draw_rect(rect, color);
-        }
+        let colors: Vec<Color> = match self.phosphor.as_mut() {
+            Some(phosphor) => phosphor.apply(vram, &self.palette),
+            None => vram
+                .value()
+                .iter()
+                .map(|value| self.palette.color(*value))
+                .collect(),
+        };
+
+        // The borders around the screen
+        self.api.clear();
+        self.api.draw_frame((vram.w(), vram.h()), &colors, area)
     }
 
     fn try_beep(&mut self) {
+        let pattern = self.interpreter.audio_pattern();
+
+        if pattern != self.pattern {
+            if let Some(pattern) = pattern {
+                self.api.set_pattern(pattern);
+            }
+            self.pattern = pattern;
+        }
+
         if self.interpreter.beep() == true {
             self.api.resume_beep();
         } else {
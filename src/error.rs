@@ -4,12 +4,34 @@ use thiserror::Error;
 pub enum ChipError {
     #[error("Unable to read this file: {0}")]
     ReadFile(String),
+    #[error("Unable to write this file: {0}")]
+    WriteFile(String),
+    #[error("Unable to load the state: {0}")]
+    LoadState(String),
+    #[error("Unable to assemble, line {line}: {message}")]
+    Assemble { line: usize, message: String },
+    #[error("Unable to load the ROM database: {0}")]
+    LoadDatabase(String),
+    #[error("Unable to load the movie: {0}")]
+    LoadMovie(String),
+    #[error("Unable to parse this color: {0}")]
+    ParseColor(String),
     #[error("Unable to load the program")]
     LoadProgram,
     #[error("Unable to use this API")]
     UseApi,
     #[error("Unable to use this interpreter")]
     UseIntepreter,
+    #[error("Unable to use this quirks preset")]
+    UseQuirks,
+    #[error("Unable to use this key mapping: {0}")]
+    UseKeyMap(String),
+    #[error("Unable to use this palette theme: {0}")]
+    UseTheme(String),
+    #[error("Unable to use this scaling mode: {0}")]
+    UseScaleMode(String),
+    #[error("Unable to draw the screen: {0}")]
+    DrawScreen(String),
     #[error("Unknown error")]
     Unknown,
 }
@@ -1,5 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 /// Crate enum for the pressed hotkeys
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Hotkey {
     Backspace,
     Tab,
@@ -239,7 +241,7 @@ pub enum Hotkey {
 }
 
 /// Crate enum for the pressed mouse buttons
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum MouseClick {
     Left,
     Middle,
@@ -248,7 +250,7 @@ pub enum MouseClick {
 }
 
 /// Mouse down button with coordinates
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Mouse {
     /// Mouse button type
     pub click: MouseClick,
@@ -268,62 +270,94 @@ impl Mouse {
     }
 }
 
+/// Crate enum for the pressed gamepad buttons
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadButton {
+    A,
+    B,
+    X,
+    Y,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Misc,
+}
+
 /// Inputs
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Input {
     Hotkey(Hotkey),
     Mouse(Mouse),
+    Gamepad(GamepadButton),
 }
 
-impl From<Input> for Option<usize> {
-    fn from(input: Input) -> Self {
-        let key_index = match input {
-            Input::Hotkey(key) => {
-                match key {
-                    // Line 1
-                    Hotkey::Num1 | Hotkey::Kp1 => 0x01,
-                    Hotkey::Num2 | Hotkey::Kp2 => 0x02,
-                    Hotkey::Num3 | Hotkey::Kp3 => 0x03,
-                    Hotkey::Num4 | Hotkey::Kp4 => 0x0c,
-
-                    // Line 1
-                    Hotkey::A => 0x04,
-                    Hotkey::Z => 0x05,
-                    Hotkey::E => 0x06,
-                    Hotkey::R => 0x0d,
-
-                    // Line 3
-                    Hotkey::Q => 0x07,
-                    Hotkey::S => 0x08,
-                    Hotkey::D => 0x09,
-                    Hotkey::F => 0x0e,
+/// Transition of a key, a press or a release edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyEvent<T> {
+    /// The key has just been pressed
+    Down(T),
+    /// The key has just been released
+    Up(T),
+}
 
-                    // Line 4
-                    Hotkey::W => 0x0a,
-                    Hotkey::X => 0x00,
-                    Hotkey::C => 0x0b,
-                    Hotkey::V => 0x0f,
-                    _ => return None,
-                }
-            }
-            Input::Mouse(_) => return None,
-        };
+/// Held keys, updated from snapshots or from press and release edges
+#[derive(Debug, Clone)]
+pub struct KeyState<T> {
+    held: Vec<T>,
+}
 
-        Some(key_index)
+impl<T> Default for KeyState<T> {
+    fn default() -> Self {
+        Self { held: Vec::new() }
     }
 }
 
-impl Input {
-    /// Convert a vector or `Input` into a vector of `usize`
-    pub fn to_keys(inputs: Vec<Input>) -> Vec<usize> {
-        let mut ret = Vec::new();
+impl<T: Copy + PartialEq> KeyState<T> {
+    /// Store the held keys, return the transitions since the previous snapshot
+    ///
+    /// The releases come before the presses.
+    pub fn update(&mut self, held: &[T]) -> Vec<KeyEvent<T>> {
+        let released = self
+            .held
+            .iter()
+            .filter(|key| !held.contains(key))
+            .map(|key| KeyEvent::Up(*key));
+        let pressed = held
+            .iter()
+            .filter(|key| !self.held.contains(key))
+            .map(|key| KeyEvent::Down(*key));
+        let events = released.chain(pressed).collect();
+
+        self.held = held.to_vec();
+        events
+    }
 
-        for input in inputs {
-            if let Some(value) = Option::<usize>::from(input) {
-                ret.push(value);
+    /// Apply press and release edges, in order
+    pub fn apply(&mut self, events: &[KeyEvent<T>]) {
+        for event in events {
+            match *event {
+                KeyEvent::Down(key) if !self.held.contains(&key) => self.held.push(key),
+                KeyEvent::Down(_) => {}
+                KeyEvent::Up(key) => self.held.retain(|held| *held != key),
             }
         }
+    }
+
+    /// Return the held keys
+    pub fn held(&self) -> &[T] {
+        &self.held
+    }
 
-        ret
+    /// Return true if the key is held
+    pub fn is_held(&self, key: T) -> bool {
+        self.held.contains(&key)
     }
 }
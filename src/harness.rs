@@ -0,0 +1,236 @@
+use std::{fmt::Display, fs, path::Path};
+
+use crate::{
+    apis::libs::headless::{HeadlessApi, HeadlessProp, InputScript},
+    emulator::FRAME_RATE,
+    error::ChipError,
+    interpreters::{quirks::Quirks, types::InterpreterType},
+    models::{api::Api, interpreter::Interpreter},
+    properties::vram::Vram,
+};
+
+/// Characters of the ASCII-art screens, by pixel value (XO-CHIP planes)
+const PIXELS: [char; 4] = ['.', '#', '+', '*'];
+
+/// Environment variable, when set the fixtures are written instead of compared
+pub const UPDATE_FIXTURES: &str = "TINYCHIP_UPDATE_FIXTURES";
+
+/// Return the ASCII-art form of a screen, one line per row
+///
+/// An empty pixel is `.`, a pixel of the first plane `#`, of the second plane `+`
+/// and of both planes `*`.
+pub fn ascii(vram: &Vram) -> String {
+    vram.value()
+        .chunks(vram.w())
+        .map(|row| {
+            row.iter()
+                .map(|pixel| PIXELS[*pixel as usize % PIXELS.len()])
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Difference between an expected ASCII-art screen and the actual one
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenDiff {
+    pub expected: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+impl ScreenDiff {
+    /// Compare two ASCII-art screens, the trailing whitespaces are ignored
+    pub fn new(expected: &str, actual: &str) -> Option<Self> {
+        let lines = |screen: &str| -> Vec<String> {
+            let mut lines: Vec<String> = screen.lines().map(|l| l.trim_end().to_string()).collect();
+
+            while lines.last().is_some_and(|line| line.is_empty()) {
+                lines.pop();
+            }
+            lines
+        };
+        let (expected, actual) = (lines(expected), lines(actual));
+
+        (expected != actual).then_some(Self { expected, actual })
+    }
+}
+
+impl Display for ScreenDiff {
+    /// The mismatching rows, with the expected one above the actual one
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self.expected.len().max(self.actual.len());
+        let empty = String::new();
+
+        writeln!(f, "The screen does not match the expected one")?;
+
+        for row in 0..rows {
+            let expected = self.expected.get(row).unwrap_or(&empty);
+            let actual = self.actual.get(row).unwrap_or(&empty);
+
+            if expected == actual {
+                continue;
+            }
+
+            let marks: String = (0..expected.len().max(actual.len()))
+                .map(|i| match expected.chars().nth(i) == actual.chars().nth(i) {
+                    true => ' ',
+                    false => '^',
+                })
+                .collect();
+
+            writeln!(f, "row {:>3} expected | {}", row, expected)?;
+            writeln!(f, "          actual | {}", actual)?;
+            writeln!(f, "                 | {}", marks)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Return a headless API playing the input script
+fn headless(script: InputScript) -> HeadlessApi {
+    let prop = HeadlessProp {
+        script,
+        ..HeadlessProp::default()
+    };
+
+    HeadlessApi::new(64, 32, prop)
+}
+
+/// Runs a ROM without any window, to compare its screen with ASCII-art fixtures
+///
+/// The runs are deterministic, the random numbers generator is seeded with 0
+/// by default. Every `cycles_per_frame` cycles the timers are decremented and
+/// the scripted inputs are read from a headless API.
+pub struct Harness {
+    interpreter: Box<dyn Interpreter>,
+    /// Plays the input script
+    api: HeadlessApi,
+    /// Cycles between two timers ticks
+    cycles_per_frame: u64,
+    /// Elapsed cycles
+    cycle: u64,
+}
+
+impl Harness {
+    pub fn new(kind: InterpreterType) -> Self {
+        let mut interpreter: Box<dyn Interpreter> = kind.into();
+
+        interpreter.set_seed(0);
+
+        Self {
+            interpreter,
+            api: headless(InputScript::new()),
+            cycles_per_frame: 500 / FRAME_RATE,
+            cycle: 0,
+        }
+    }
+
+    /// Set the compatibility behaviors
+    pub fn set_quirks(mut self, quirks: Quirks) -> Self {
+        self.interpreter.set_quirks(quirks);
+
+        self
+    }
+
+    /// Seed the random numbers generator
+    pub fn set_seed(mut self, seed: u64) -> Self {
+        self.interpreter.set_seed(seed);
+
+        self
+    }
+
+    /// Set the inputs held from a given frame, until the next entry
+    pub fn set_inputs(mut self, script: InputScript) -> Self {
+        self.api = headless(script);
+
+        self
+    }
+
+    /// Set the number of cycles between two timers ticks
+    pub fn set_cycles_per_frame(mut self, cycles: u64) -> Self {
+        self.cycles_per_frame = cycles.max(1);
+
+        self
+    }
+
+    /// Load the program
+    pub fn load<T: Into<Vec<u8>>>(mut self, program: T) -> Result<Self, ChipError> {
+        self.interpreter.load_program(program.into())?;
+
+        Ok(self)
+    }
+
+    /// Load the program from a file
+    pub fn load_file<P: AsRef<Path>>(self, path: P) -> Result<Self, ChipError> {
+        let program = fs::read(path).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+
+        self.load(program)
+    }
+
+    /// Run `cycles` cycles, or less if the program exits
+    pub fn run(&mut self, cycles: u64) -> &mut Self {
+        for _ in 0..cycles {
+            if self.interpreter.exited() {
+                break;
+            }
+
+            // The input edges are given at the first cycle of a frame
+            let events = match self.cycle.is_multiple_of(self.cycles_per_frame) {
+                true => self.api.events(),
+                false => Vec::new(),
+            };
+
+            self.interpreter.step(&events);
+            self.cycle += 1;
+
+            if self.cycle.is_multiple_of(self.cycles_per_frame) {
+                self.interpreter.timers_tick();
+            }
+        }
+
+        self
+    }
+
+    /// Return the interpreter, to check its registers or memory
+    pub fn interpreter(&self) -> &dyn Interpreter {
+        self.interpreter.as_ref()
+    }
+
+    /// Return the elapsed cycles
+    pub fn cycles(&self) -> u64 {
+        self.cycle
+    }
+
+    /// Return the ASCII-art form of the screen
+    pub fn screen(&self) -> String {
+        ascii(self.interpreter.vram())
+    }
+
+    /// Compare the screen with an ASCII-art one
+    pub fn compare(&self, expected: &str) -> Result<(), ScreenDiff> {
+        match ScreenDiff::new(expected, &self.screen()) {
+            Some(diff) => Err(diff),
+            None => Ok(()),
+        }
+    }
+
+    /// Panic with the diff if the screen does not match the ASCII-art fixture file
+    ///
+    /// With the `TINYCHIP_UPDATE_FIXTURES` environment variable, the fixture is written instead.
+    pub fn assert_fixture<P: AsRef<Path>>(&self, path: P) {
+        let path = path.as_ref();
+
+        if std::env::var_os(UPDATE_FIXTURES).is_some() {
+            fs::write(path, self.screen() + "\n").unwrap();
+            return;
+        }
+
+        let expected = fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("Unable to read {}: {}", path.display(), e));
+
+        if let Err(diff) = self.compare(&expected) {
+            panic!("{}: {}", path.display(), diff);
+        }
+    }
+}
@@ -1,15 +1,32 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use sha1::{Digest, Sha1};
 
 use crate::{
     apis::api::{RECTS_X, RECTS_Y},
-    event::Input,
-    models::{instructions::Instructions, interpreter::Interpreter, memory::Memory},
-    properties::{clock::Clock, opcode::Opcode, vram::Vram},
+    error::ChipError,
+    event::{Input, KeyEvent, KeyState},
+    keymap::KeyMap,
+    models::{
+        instructions::Instructions, interpreter::Interpreter, memory::Memory,
+        super_instructions::SuperInstructions, xo_instructions::XoInstructions,
+    },
+    properties::{
+        opcode::Opcode,
+        pattern::{AudioPattern, PATTERN_SIZE},
+        registers::Registers,
+        vram::Vram,
+    },
 };
 
-use crate::interpreters::pc::ProgramCount;
+use crate::interpreters::{
+    pc::ProgramCount,
+    quirks::Quirks,
+    state::{StateReader, StateWriter, STATE_MAGIC, STATE_VERSION},
+    types::InterpreterType,
+};
 
-use super::pc::{ProgramCountState, OPCODE_SIZE};
+use super::pc::{ProgramCountState, OPCODE_SIZE, PROGRAM_START};
 
 /// Font
 const FONT: [u8; 5 * 16] = [
@@ -31,11 +48,68 @@ const FONT: [u8; 5 * 16] = [
     0xf0, 0x80, 0xf0, 0x80, 0x80, // F
 ];
 
+/// SUPER-CHIP big font (8x10)
+const BIG_FONT: [u8; 10 * 16] = [
+    0xff, 0xff, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3, 0xff, 0xff, // 0
+    0x18, 0x78, 0x78, 0x18, 0x18, 0x18, 0x18, 0x18, 0xff, 0xff, // 1
+    0xff, 0xff, 0x03, 0x03, 0xff, 0xff, 0xc0, 0xc0, 0xff, 0xff, // 2
+    0xff, 0xff, 0x03, 0x03, 0xff, 0xff, 0x03, 0x03, 0xff, 0xff, // 3
+    0xc3, 0xc3, 0xc3, 0xc3, 0xff, 0xff, 0x03, 0x03, 0x03, 0x03, // 4
+    0xff, 0xff, 0xc0, 0xc0, 0xff, 0xff, 0x03, 0x03, 0xff, 0xff, // 5
+    0xff, 0xff, 0xc0, 0xc0, 0xff, 0xff, 0xc3, 0xc3, 0xff, 0xff, // 6
+    0xff, 0xff, 0x03, 0x03, 0x06, 0x0c, 0x18, 0x18, 0x18, 0x18, // 7
+    0xff, 0xff, 0xc3, 0xc3, 0xff, 0xff, 0xc3, 0xc3, 0xff, 0xff, // 8
+    0xff, 0xff, 0xc3, 0xc3, 0xff, 0xff, 0x03, 0x03, 0xff, 0xff, // 9
+    0x7e, 0xff, 0xc3, 0xc3, 0xc3, 0xff, 0xff, 0xc3, 0xc3, 0xc3, // A
+    0xfc, 0xfc, 0xc3, 0xc3, 0xfc, 0xfc, 0xc3, 0xc3, 0xfc, 0xfc, // B
+    0x3c, 0xff, 0xc3, 0xc0, 0xc0, 0xc0, 0xc0, 0xc3, 0xff, 0x3c, // C
+    0xfc, 0xfe, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3, 0xc3, 0xfe, 0xfc, // D
+    0xff, 0xff, 0xc0, 0xc0, 0xff, 0xff, 0xc0, 0xc0, 0xff, 0xff, // E
+    0xff, 0xff, 0xc0, 0xc0, 0xff, 0xff, 0xc0, 0xc0, 0xc0, 0xc0, // F
+];
+
+/// Big font location in memory
+const BIG_FONT_ADDR: usize = 0x50;
+
+/// XO-CHIP memory size
+const XO_RAM_SIZE: usize = 0x10000;
+
+/// XO-CHIP long load prefix (F000 nnnn)
+const LONG_LOAD: u16 = 0xf000;
+
 /// Interpreter state
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum InterpreterState {
     Running,
     WaitForKey,
+    Exit,
+}
+
+impl From<InterpreterState> for u8 {
+    fn from(state: InterpreterState) -> Self {
+        match state {
+            InterpreterState::Running => 0,
+            InterpreterState::WaitForKey => 1,
+            InterpreterState::Exit => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for InterpreterState {
+    type Error = ChipError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let state = match value {
+            0 => Self::Running,
+            1 => Self::WaitForKey,
+            2 => Self::Exit,
+            _ => {
+                return Err(ChipError::LoadState(String::from("invalid state")));
+            }
+        };
+
+        Ok(state)
+    }
 }
 
 /// First chip8 interpreter
@@ -43,9 +117,11 @@ pub struct ChipInterpreter {
     /// Memory
     ///
     /// 0x000-0x1ff - Chip 8 interpreter
-    /// 0x050-0x0a0 - Used for the built in 4x5 pixel font set (0-f)
+    /// 0x000-0x050 - Used for the built in 4x5 pixel font set (0-f)
+    /// 0x050-0x0f0 - Used for the built in 8x10 pixel font set (0-f), SUPER-CHIP only
     /// 0x200-0x0fff - Program ROM and RAM
-    ram: [u8; 4096],
+    /// 0x200-0xffff - Program ROM and RAM, XO-CHIP only
+    ram: Vec<u8>,
     /// Current opcode
     opcode: Opcode,
     /// CPU Registers
@@ -66,50 +142,133 @@ pub struct ChipInterpreter {
     sp: u16,
     /// Keys
     key: [u8; 16],
+    /// Inputs held, from their press and release edges
+    inputs: KeyState<Input>,
+    /// Keypad keys held at the previous cycle
+    keypad: KeyState<u8>,
+    /// Key pressed while waiting for its release (fx0a)
+    wait_key: Option<u8>,
+    /// Keyboard to keypad mapping
+    keymap: KeyMap,
     /// Interpreter state
     state: InterpreterState,
     /// Controlling the screen display
     display: bool,
-    /// Load semantic
-    original_load: bool,
-    /// Shift semantic
-    original_shift: bool,
-    /// Timers clock
-    timers_clock: Clock,
+    /// Compatibility behaviors
+    quirks: Quirks,
+    /// A sprite has been drawn during the current frame
+    frame_drawn: bool,
+    /// Interpreter type
+    kind: InterpreterType,
+    /// RPL user flags
+    rpl: [u8; 16],
+    /// Random numbers generator (cxkk), its position is saved with the state
+    rng: ChaCha12Rng,
+    /// Selected drawing planes (bitmask)
+    planes: u8,
+    /// Audio pattern
+    pattern: Option<AudioPattern>,
+    /// Loaded program SHA-1
+    rom_hash: [u8; 20],
 }
 
 impl Default for ChipInterpreter {
     fn default() -> Self {
         Self {
-            ram: [0; 4096],
+            ram: vec![0; 4096],
             opcode: 0x0000.into(),
             v: [0; 16],
             i: 0,
-            pc: ProgramCount::from(0x200),
+            pc: ProgramCount::from(PROGRAM_START),
             vram: Vram::default(),
             delay_timer: 0,
             sound_timer: 0,
             stack: [0; 16],
             sp: 0,
             key: [0; 16],
+            inputs: KeyState::default(),
+            keypad: KeyState::default(),
+            wait_key: None,
+            keymap: KeyMap::default(),
             state: InterpreterState::Running,
             display: false,
-            original_load: false,
-            original_shift: false,
-            timers_clock: Clock::new(1_000_000 / 60),
+            quirks: Quirks::default(),
+            frame_drawn: false,
+            kind: InterpreterType::Original,
+            rpl: [0; 16],
+            rng: ChaCha12Rng::from_entropy(),
+            planes: 1,
+            pattern: None,
+            rom_hash: [0; 20],
         }
     }
 }
 
 impl ChipInterpreter {
     pub fn new() -> Self {
-        let mut interpreter = Self::default();
+        Self::with_type(InterpreterType::Original)
+    }
 
-        // Load the font
+    pub fn with_type(kind: InterpreterType) -> Self {
+        let mut interpreter = Self {
+            kind,
+            ..Self::default()
+        };
+
+        if interpreter.is_xo() {
+            interpreter.ram = vec![0; XO_RAM_SIZE];
+        }
+
+        // Load the font(s)
         interpreter.write_any(FONT.to_vec(), 0);
+        if interpreter.is_super() {
+            interpreter.write_any(BIG_FONT.to_vec(), BIG_FONT_ADDR);
+        }
         interpreter
     }
 
+    /// Return true if the SUPER-CHIP instructions are supported
+    fn is_super(&self) -> bool {
+        self.kind != InterpreterType::Original
+    }
+
+    /// Return true if the XO-CHIP instructions are supported
+    fn is_xo(&self) -> bool {
+        self.kind == InterpreterType::Xochip
+    }
+
+    /// Skip the next instruction
+    ///
+    /// With XO-CHIP, the next instruction can be 4 bytes long
+    fn skip(&mut self) {
+        let next = self.pc.value.wrapping_add(OPCODE_SIZE);
+
+        if self.is_xo() && self.read_short(next as usize) == LONG_LOAD {
+            self.pc
+                .set_state(ProgramCountState::Jump(next.wrapping_add(OPCODE_SIZE * 2)));
+        } else {
+            self.pc.set_state(ProgramCountState::Skip);
+        }
+    }
+
+    /// Return the last RPL flag index used by Fx75 and Fx85
+    fn rpl_index(&self) -> usize {
+        let max = if self.is_xo() { 15 } else { 7 };
+
+        (self.opcode.x() as usize).min(max)
+    }
+
+    /// Return the register indexes between x and y (included), in the opcode order
+    fn register_range(&self) -> Vec<usize> {
+        let (x, y) = (self.opcode.x() as usize, self.opcode.y() as usize);
+
+        if x <= y {
+            (x..=y).collect()
+        } else {
+            (y..=x).rev().collect()
+        }
+    }
+
     /// CPU register at index `x`
     fn vx(&self) -> u8 {
         self.v[self.opcode.x() as usize]
@@ -125,6 +284,12 @@ impl ChipInterpreter {
         self.v[self.opcode.x() as usize] = byte;
     }
 
+    /// Set Vx then VF, the flag wins when x is F
+    fn set_vx_vf(&mut self, byte: u8, flag: u8) {
+        self.set_vx(byte);
+        self.v[0x0f] = flag;
+    }
+
     /// Set the CPU register at index `y`
     #[allow(dead_code)]
     fn set_vy(&mut self, byte: u8) {
@@ -139,33 +304,131 @@ impl ChipInterpreter {
     }
 
     /// Assign keyboard hotkeys to the CHIP8 hotkeys
-    fn assign_keys(&mut self, keys: Vec<usize>) {
+    fn assign_keys(&mut self, keys: &[u8]) {
         for index in keys {
-            self.key[index] = 1;
+            self.key[*index as usize] = 1;
         }
     }
 
-    fn timers_tick(&mut self) {
-        if self.timers_clock.try_reset() == false {
-            return;
+    /// Turn the input edges into keypad edges
+    ///
+    /// A key held by several inputs is pressed by the first one and released by the last one.
+    fn keypad_events(&self, events: &[KeyEvent<Input>], previous: &[u8]) -> Vec<KeyEvent<u8>> {
+        let key = |input: &Input| self.keymap.keys(&[*input]).first().map(|key| *key as u8);
+
+        events
+            .iter()
+            .filter_map(|event| match event {
+                KeyEvent::Down(input) => key(input)
+                    .filter(|key| !previous.contains(key))
+                    .map(KeyEvent::Down),
+                KeyEvent::Up(input) => key(input)
+                    .filter(|key| !self.keypad.is_held(*key))
+                    .map(KeyEvent::Up),
+            })
+            .collect()
+    }
+
+    /// Return the key completing fx0a, if any
+    ///
+    /// Only a new press counts, so a held key does not trigger it again.
+    fn waited_key(&mut self, events: &[KeyEvent<u8>]) -> Option<u8> {
+        for event in events {
+            match *event {
+                KeyEvent::Down(key) if !self.quirks.key_release => return Some(key),
+                KeyEvent::Down(key) => {
+                    self.wait_key.get_or_insert(key);
+                }
+                KeyEvent::Up(key) if self.wait_key == Some(key) => {
+                    self.wait_key = None;
+                    return Some(key);
+                }
+                KeyEvent::Up(_) => {}
+            }
         }
 
-        if self.delay_timer > 0 {
-            self.delay_timer -= 1;
+        None
+    }
+
+    /// XOR a sprite of `w` x `h` pixels at (Vx, Vy), set VF = collision
+    ///
+    /// Each selected plane reads its own sprite, one after the other in memory
+    fn draw_sprite(&mut self, w: usize, h: usize) {
+        // The starting position always wraps
+        let vx = self.vx() as usize % self.vram.w();
+        let vy = self.vy() as usize % self.vram.h();
+        let row_size = w / 8;
+        let mut addr = self.i as usize;
+
+        self.v[0x0f] = 0;
+
+        for plane in (0..8).map(|bit| 1 << bit) {
+            if self.planes & plane == 0 {
+                continue;
+            }
+
+            for row in 0..h {
+                if self.quirks.clip && vy + row >= self.vram.h() {
+                    break;
+                }
+
+                let y = (vy + row) % self.vram.h();
+
+                for bit in 0..w {
+                    if self.quirks.clip && vx + bit >= self.vram.w() {
+                        break;
+                    }
+
+                    let x = (vx + bit) % self.vram.w();
+                    let value = self.read_byte(addr + row * row_size + bit / 8);
+                    let color = (value >> (7 - bit % 8) & 1) * plane;
+                    let vram_byte = self.vram.get(x, y);
+
+                    self.v[0x0f] |= (color & vram_byte != 0) as u8;
+                    self.vram.put(x, y, vram_byte ^ color);
+                }
+            }
+
+            addr += row_size * h;
         }
-        if self.sound_timer > 0 {
-            self.sound_timer -= 1;
+
+        self.display = true;
+    }
+
+    /// Reset VF after 8xy1, 8xy2 and 8xy3, depending on the quirks
+    fn vf_reset(&mut self) {
+        if self.quirks.vf_reset {
+            self.v[0x0f] = 0;
         }
     }
+
+    /// Return true if the draw has to wait for the next frame
+    ///
+    /// The current instruction is executed again at the next cycle
+    fn wait_vblank(&mut self) -> bool {
+        if !self.quirks.display_wait {
+            return false;
+        }
+
+        if self.frame_drawn {
+            self.pc.set_state(ProgramCountState::Jump(self.pc.value));
+            return true;
+        }
+
+        self.frame_drawn = true;
+        false
+    }
 }
 
 impl Memory for ChipInterpreter {
     fn write_byte_at(&mut self, byte: u8, index: usize) {
-        self.ram[index] = byte;
+        let len = self.ram.len();
+
+        self.ram[index % len] = byte;
     }
 
     fn read_byte(&self, index: usize) -> u8 {
-        self.ram[index]
+        self.ram[index % self.ram.len()]
     }
 
     fn read_short(&self, index: usize) -> u16 {
@@ -182,7 +445,7 @@ impl Instructions for ChipInterpreter {
     }
 
     fn cls(&mut self) {
-        self.vram.clear();
+        self.vram.clear_planes(self.planes);
 
         self.display = true;
     }
@@ -201,7 +464,7 @@ impl Instructions for ChipInterpreter {
     }
 
     fn call(&mut self) {
-        self.stack[self.sp as usize] = self.pc.value + OPCODE_SIZE;
+        self.stack[self.sp as usize] = self.pc.value.wrapping_add(OPCODE_SIZE);
 
         let state = ProgramCountState::Jump(self.opcode.nnn());
 
@@ -211,19 +474,19 @@ impl Instructions for ChipInterpreter {
 
     fn se_vx_byte(&mut self) {
         if self.vx() == self.opcode.kk() {
-            self.pc.set_state(ProgramCountState::Skip);
+            self.skip();
         }
     }
 
     fn sne_vx_byte(&mut self) {
         if self.vx() != self.opcode.kk() {
-            self.pc.set_state(ProgramCountState::Skip);
+            self.skip();
         }
     }
 
     fn se_vx_vy(&mut self) {
         if self.vx() == self.vy() {
-            self.pc.set_state(ProgramCountState::Skip);
+            self.skip();
         }
     }
 
@@ -244,80 +507,76 @@ impl Instructions for ChipInterpreter {
 
     fn or_vx_vy(&mut self) {
         self.set_vx(self.vx() | self.vy());
+        self.vf_reset();
     }
 
     fn and_vx_vy(&mut self) {
         self.set_vx(self.vx() & self.vy());
+        self.vf_reset();
     }
 
     fn xor_vx_vy(&mut self) {
         self.set_vx(self.vx() ^ self.vy());
+        self.vf_reset();
     }
 
     fn add_vx_vy(&mut self) {
-        let sum = (self.vx() as u16) + (self.vy() as u16);
-
-        self.v[0x0f] = (sum > 0xff) as u8;
+        let (sum, carry) = self.vx().overflowing_add(self.vy());
 
-        // Lowest 8 bits
-        self.set_vx((sum & 0xff) as u8);
+        self.set_vx_vf(sum, carry as u8);
     }
 
     fn sub_vx_vy(&mut self) {
         let vx = self.vx();
         let vy = self.vy();
 
-        self.v[0x0f] = (vx > vy) as u8;
-        self.set_vx(vx.wrapping_sub(vy));
+        // VF = NOT borrow
+        self.set_vx_vf(vx.wrapping_sub(vy), (vx >= vy) as u8);
     }
 
     fn shr_vx_vy(&mut self) {
-        if self.original_shift == true {
+        if self.quirks.shift {
             return self.shr_vx_vy_original();
         }
 
-        let vx = self.v[self.opcode.x() as usize];
+        let vx = self.vx();
 
-        self.v[0x0f] = vx & 1;
-        self.set_vx(vx >> 1);
+        self.set_vx_vf(vx >> 1, vx & 1);
     }
 
     fn shr_vx_vy_original(&mut self) {
-        let vx = self.v[self.opcode.x() as usize];
+        let vy = self.vy();
 
-        self.v[0x0f] = vx & 1;
-        self.set_vx(self.vy() >> 1);
+        self.set_vx_vf(vy >> 1, vy & 1);
     }
 
     fn subn_vx_vy(&mut self) {
         let vx = self.vx();
         let vy = self.vy();
 
-        self.v[0x0f] = (vy > vx) as u8;
-        self.set_vx(vy.wrapping_sub(vx));
+        // VF = NOT borrow
+        self.set_vx_vf(vy.wrapping_sub(vx), (vy >= vx) as u8);
     }
 
     fn shl_vx_vy(&mut self) {
-        if self.original_shift == true {
+        if self.quirks.shift {
             return self.shl_vx_vy_original();
         }
 
         let vx = self.vx();
 
-        self.v[0x0f] = vx >> 7;
-        self.set_vx(vx << 1);
+        self.set_vx_vf(vx << 1, vx >> 7);
     }
 
     fn shl_vx_vy_original(&mut self) {
-        let vx = self.vx();
+        let vy = self.vy();
 
-        self.v[0x0f] = vx >> 7;
-        self.set_vx(self.vy() << 1);
+        self.set_vx_vf(vy << 1, vy >> 7);
     }
 
     fn sne_vx_vy(&mut self) {
         if self.vx() != self.vy() {
-            self.pc.set_state(ProgramCountState::Skip);
+            self.skip();
         }
     }
 
@@ -326,46 +585,39 @@ impl Instructions for ChipInterpreter {
     }
 
     fn jp_v(&mut self) {
-        let addr = self.opcode.nnn() + (self.v[0] as u16);
+        let register = if self.quirks.jump {
+            self.vx()
+        } else {
+            self.v[0]
+        };
+        let addr = self.opcode.nnn() + (register as u16);
 
         self.pc.set_state(ProgramCountState::Jump(addr));
     }
 
     fn rnd_vx_byte(&mut self) {
-        let byte = rand::thread_rng().gen::<u8>() & self.opcode.kk();
+        let byte = self.rng.gen::<u8>() & self.opcode.kk();
 
         self.set_vx(byte);
     }
 
     fn drw_vx_vy_n(&mut self) {
-        self.v[0x0f] = 0;
-
-        for byte in 0..(self.opcode.n() as usize) {
-            let y = (self.vy() as usize + byte) % RECTS_Y as usize;
-            let value = self.read_byte(self.i as usize + byte);
-
-            for bit in 0..8 {
-                let x = (self.vx() as usize + bit) % RECTS_X as usize;
-                let color = value >> (7 - bit) & 1;
-                let vram_byte = self.vram.get(x, y);
-
-                self.v[0x0f] |= color & vram_byte;
-                self.vram.put(x, y, vram_byte ^ color);
-            }
+        if self.wait_vblank() {
+            return;
         }
 
-        self.display = true;
+        self.draw_sprite(8, self.opcode.n() as usize);
     }
 
     fn skp_vx(&mut self) {
         if self.key[self.vx() as usize] == 1 {
-            self.pc.set_state(ProgramCountState::Skip);
+            self.skip();
         }
     }
 
     fn sknp_vx(&mut self) {
         if self.key[self.vx() as usize] == 0 {
-            self.pc.set_state(ProgramCountState::Skip);
+            self.skip();
         }
     }
 
@@ -375,6 +627,7 @@ impl Instructions for ChipInterpreter {
 
     fn ld_vx_k(&mut self) {
         self.state = InterpreterState::WaitForKey;
+        self.wait_key = None;
     }
 
     fn ld_dt_vx(&mut self) {
@@ -386,7 +639,11 @@ impl Instructions for ChipInterpreter {
     }
 
     fn add_i_vx(&mut self) {
-        self.i += self.vx() as u16;
+        self.i = self.i.wrapping_add(self.vx() as u16);
+
+        if self.quirks.index_overflow {
+            self.v[0x0f] = (self.i > 0x0fff) as u8;
+        }
     }
 
     fn ld_f_vx(&mut self) {
@@ -403,7 +660,7 @@ impl Instructions for ChipInterpreter {
     }
 
     fn ld_i_vx(&mut self) {
-        if self.original_load == true {
+        if self.quirks.load {
             return self.ld_i_vx_original();
         }
 
@@ -420,7 +677,7 @@ impl Instructions for ChipInterpreter {
     }
 
     fn ld_vx_i(&mut self) {
-        if self.original_load == true {
+        if self.quirks.load {
             return self.ld_vx_i_original();
         }
 
@@ -439,13 +696,158 @@ impl Instructions for ChipInterpreter {
     }
 }
 
+impl SuperInstructions for ChipInterpreter {
+    fn scd_n(&mut self) {
+        self.vram.scroll(0, self.opcode.n() as isize, self.planes);
+
+        self.display = true;
+    }
+
+    fn scr(&mut self) {
+        self.vram.scroll(4, 0, self.planes);
+
+        self.display = true;
+    }
+
+    fn scl(&mut self) {
+        self.vram.scroll(-4, 0, self.planes);
+
+        self.display = true;
+    }
+
+    fn exit(&mut self) {
+        self.state = InterpreterState::Exit;
+    }
+
+    fn low(&mut self) {
+        self.vram.resize(RECTS_X as usize, RECTS_Y as usize);
+
+        self.display = true;
+    }
+
+    fn high(&mut self) {
+        self.vram.resize(RECTS_X as usize * 2, RECTS_Y as usize * 2);
+
+        self.display = true;
+    }
+
+    fn drw_vx_vy_0(&mut self) {
+        if self.wait_vblank() {
+            return;
+        }
+
+        self.draw_sprite(16, 16);
+    }
+
+    fn ld_hf_vx(&mut self) {
+        self.i = (BIG_FONT_ADDR + (self.vx() as usize & 0x0f) * 10) as u16;
+    }
+
+    fn ld_r_vx(&mut self) {
+        let x = self.rpl_index();
+
+        self.rpl[..=x].copy_from_slice(&self.v[..=x]);
+    }
+
+    fn ld_vx_r(&mut self) {
+        let x = self.rpl_index();
+
+        self.v[..=x].copy_from_slice(&self.rpl[..=x]);
+    }
+}
+
+impl XoInstructions for ChipInterpreter {
+    fn scu_n(&mut self) {
+        self.vram
+            .scroll(0, -(self.opcode.n() as isize), self.planes);
+
+        self.display = true;
+    }
+
+    fn ld_i_vx_vy(&mut self) {
+        for (offset, register) in self.register_range().into_iter().enumerate() {
+            self.write_byte_at(self.v[register], self.i as usize + offset);
+        }
+    }
+
+    fn ld_vx_vy_i(&mut self) {
+        for (offset, register) in self.register_range().into_iter().enumerate() {
+            self.v[register] = self.read_byte(self.i as usize + offset);
+        }
+    }
+
+    fn ld_i_long(&mut self) {
+        let next = self.pc.value.wrapping_add(OPCODE_SIZE);
+
+        self.i = self.read_short(next as usize);
+        self.pc
+            .set_state(ProgramCountState::Jump(next.wrapping_add(OPCODE_SIZE)));
+    }
+
+    fn plane_n(&mut self) {
+        // Only two planes exist, the higher bits are ignored
+        self.planes = self.opcode.x() & 0b11;
+    }
+
+    fn audio(&mut self) {
+        let mut buffer = [0; PATTERN_SIZE];
+
+        for (offset, byte) in buffer.iter_mut().enumerate() {
+            *byte = self.read_byte(self.i as usize + offset);
+        }
+
+        let pitch = self.pattern.unwrap_or_default().pitch;
+
+        self.pattern = Some(AudioPattern::new(buffer, pitch));
+    }
+
+    fn pitch_vx(&mut self) {
+        let mut pattern = self.pattern.unwrap_or_default();
+
+        pattern.pitch = self.vx();
+        self.pattern = Some(pattern);
+    }
+}
+
 impl Interpreter for ChipInterpreter {
-    fn vram(&self) -> Vram {
-        self.vram.clone()
+    fn vram(&self) -> &Vram {
+        &self.vram
+    }
+
+    fn registers(&self) -> Registers {
+        Registers {
+            opcode: self.opcode.value,
+            v: self.v,
+            i: self.i,
+            pc: self.pc.value,
+            stack: self.stack,
+            sp: self.sp,
+            delay_timer: self.delay_timer,
+            sound_timer: self.sound_timer,
+        }
+    }
+
+    fn kind(&self) -> InterpreterType {
+        self.kind
     }
 
-    fn step(&mut self, inputs: Vec<Input>) -> bool {
-        let keys = Input::to_keys(inputs);
+    fn step(&mut self, events: &[KeyEvent<Input>]) -> bool {
+        self.inputs.apply(events);
+
+        let keys: Vec<u8> = self
+            .keymap
+            .keys(self.inputs.held())
+            .into_iter()
+            .map(|key| key as u8)
+            .collect();
+        let previous = self.keypad.held().to_vec();
+
+        self.keypad.update(&keys);
+        let events = self.keypad_events(events, &previous);
+
+        if self.state == InterpreterState::Exit {
+            return false;
+        }
 
         // Reset the program counter and screen display
         self.pc.reset_state();
@@ -453,34 +855,43 @@ impl Interpreter for ChipInterpreter {
 
         // Hotkeys handling
         self.reset_keys();
-        self.assign_keys(keys.clone());
+        self.assign_keys(&keys);
 
         // Listening for ld_vx_k (fx0a)
         // aka (Hotkeys halting)
         if self.state == InterpreterState::WaitForKey {
-            if let Some(value) = keys.first() {
-                self.set_vx(*value as u8);
+            if let Some(value) = self.waited_key(&events) {
+                self.set_vx(value);
                 self.state = InterpreterState::Running;
             }
             return self.display;
         }
 
-        // Upodate both timers
-        self.timers_tick();
-
         // Fetch the operation code
         self.opcode = self.read_short(self.pc.value as usize).into();
 
         // Execute the operation code
+        let is_super = self.is_super();
+        let is_xo = self.is_xo();
+
         match self.opcode.into() {
             (0x00, 0x00, 0x0e, 0x00) => self.cls(),
             (0x00, 0x00, 0x0e, 0x0e) => self.ret(),
+            (0x00, 0x00, 0x0c, _) if is_super => self.scd_n(),
+            (0x00, 0x00, 0x0d, _) if is_xo => self.scu_n(),
+            (0x00, 0x00, 0x0f, 0x0b) if is_super => self.scr(),
+            (0x00, 0x00, 0x0f, 0x0c) if is_super => self.scl(),
+            (0x00, 0x00, 0x0f, 0x0d) if is_super => self.exit(),
+            (0x00, 0x00, 0x0f, 0x0e) if is_super => self.low(),
+            (0x00, 0x00, 0x0f, 0x0f) if is_super => self.high(),
             (0x00, _, _, _) => self.sys(),
             (0x01, _, _, _) => self.jp(),
             (0x02, _, _, _) => self.call(),
             (0x03, _, _, _) => self.se_vx_byte(),
             (0x04, _, _, _) => self.sne_vx_byte(),
             (0x05, _, _, 0x00) => self.se_vx_vy(),
+            (0x05, _, _, 0x02) if is_xo => self.ld_i_vx_vy(),
+            (0x05, _, _, 0x03) if is_xo => self.ld_vx_vy_i(),
             (0x06, _, _, _) => self.ld_vx_byte(),
             (0x07, _, _, _) => self.add_vx_byte(),
             (0x08, _, _, 0x00) => self.ld_vx_vy(),
@@ -496,18 +907,26 @@ impl Interpreter for ChipInterpreter {
             (0x0a, _, _, _) => self.ld_i(),
             (0x0b, _, _, _) => self.jp_v(),
             (0x0c, _, _, _) => self.rnd_vx_byte(),
+            (0x0d, _, _, 0x00) if is_super => self.drw_vx_vy_0(),
             (0x0d, _, _, _) => self.drw_vx_vy_n(),
             (0x0e, _, 0x09, 0x0e) => self.skp_vx(),
             (0x0e, _, 0x0a, 0x01) => self.sknp_vx(),
+            (0x0f, 0x00, 0x00, 0x00) if is_xo => self.ld_i_long(),
+            (0x0f, _, 0x00, 0x01) if is_xo => self.plane_n(),
+            (0x0f, 0x00, 0x00, 0x02) if is_xo => self.audio(),
             (0x0f, _, 0x00, 0x07) => self.ld_vx_dt(),
             (0x0f, _, 0x01, 0x05) => self.ld_dt_vx(),
             (0x0f, _, 0x01, 0x08) => self.ld_st_vx(),
             (0x0f, _, 0x01, 0x0e) => self.add_i_vx(),
             (0x0f, _, 0x02, 0x09) => self.ld_f_vx(),
+            (0x0f, _, 0x03, 0x00) if is_super => self.ld_hf_vx(),
+            (0x0f, _, 0x03, 0x0a) if is_xo => self.pitch_vx(),
             (0x0f, _, 0x03, 0x03) => self.ld_b_vx(),
             (0x0f, _, 0x00, 0x0a) => self.ld_vx_k(),
             (0x0f, _, 0x05, 0x05) => self.ld_i_vx(),
             (0x0f, _, 0x06, 0x05) => self.ld_vx_i(),
+            (0x0f, _, 0x07, 0x05) if is_super => self.ld_r_vx(),
+            (0x0f, _, 0x08, 0x05) if is_super => self.ld_vx_r(),
             (_, _, _, _) => {}
         }
 
@@ -517,19 +936,234 @@ impl Interpreter for ChipInterpreter {
         self.display
     }
 
-    fn load_program(&mut self, program: Vec<u8>) {
-        self.write_any(program, 0x200);
+    fn load_program(&mut self, program: Vec<u8>) -> Result<(), ChipError> {
+        // The memory accesses wrap around, the program must not overwrite the font
+        if program.len() > self.ram.len() - PROGRAM_START as usize {
+            return Err(ChipError::LoadProgram);
+        }
+
+        self.rom_hash = Sha1::digest(&program).into();
+        self.write_any(program, PROGRAM_START as usize);
+
+        Ok(())
     }
 
     fn beep(&self) -> bool {
         self.sound_timer > 0
     }
 
-    fn set_original_load(&mut self, value: bool) {
-        self.original_load = value;
+    fn audio_pattern(&self) -> Option<AudioPattern> {
+        self.pattern
+    }
+
+    fn timers_tick(&mut self) {
+        self.frame_drawn = false;
+
+        if self.delay_timer > 0 {
+            self.delay_timer -= 1;
+        }
+        if self.sound_timer > 0 {
+            self.sound_timer -= 1;
+        }
+    }
+
+    fn exited(&self) -> bool {
+        self.state == InterpreterState::Exit
+    }
+
+    fn save_state(&self) -> Vec<u8> {
+        let mut writer = StateWriter::new();
+
+        // Header
+        writer.write_bytes(STATE_MAGIC);
+        writer.write_u8(STATE_VERSION);
+        writer.write_u8(self.kind.into());
+        writer.write_bytes(&self.rom_hash);
+
+        // CPU
+        writer.write_u32(self.ram.len() as u32);
+        writer.write_bytes(&self.ram);
+        writer.write_u16(self.opcode.value);
+        writer.write_bytes(&self.v);
+        writer.write_u16(self.i);
+        writer.write_u16(self.pc.value);
+        for addr in self.stack {
+            writer.write_u16(addr);
+        }
+        writer.write_u16(self.sp);
+        writer.write_u8(self.delay_timer);
+        writer.write_u8(self.sound_timer);
+        writer.write_u8(self.state.into());
+        writer.write_bytes(&self.rpl);
+        writer.write_bytes(&self.rng.get_seed());
+        writer.write_bytes(&self.rng.get_stream().to_be_bytes());
+        writer.write_bytes(&self.rng.get_word_pos().to_be_bytes());
+
+        // Keypad
+        writer.write_bytes(&self.key);
+        writer.write_u8(self.keypad.held().len() as u8);
+        writer.write_bytes(self.keypad.held());
+        match self.wait_key {
+            Some(key) => {
+                writer.write_u8(1);
+                writer.write_u8(key);
+            }
+            None => writer.write_u8(0),
+        }
+
+        // Graphics
+        writer.write_u8(self.frame_drawn as u8);
+        writer.write_u8(self.planes);
+        writer.write_u16(self.vram.w() as u16);
+        writer.write_u16(self.vram.h() as u16);
+        writer.write_bytes(self.vram.value());
+
+        // Audio
+        match self.pattern {
+            Some(pattern) => {
+                writer.write_u8(1);
+                writer.write_bytes(&pattern.buffer);
+                writer.write_u8(pattern.pitch);
+            }
+            None => writer.write_u8(0),
+        }
+
+        writer.into_bytes()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), ChipError> {
+        let mut reader = StateReader::new(state);
+
+        // Header
+        reader.read_header()?;
+
+        if InterpreterType::try_from(reader.read_u8()?)? != self.kind {
+            return Err(ChipError::LoadState(String::from(
+                "saved from another interpreter",
+            )));
+        }
+        if reader.read_array::<20>()? != self.rom_hash {
+            return Err(ChipError::LoadState(String::from(
+                "saved from another program",
+            )));
+        }
+
+        // CPU
+        let ram_size = reader.read_u32()? as usize;
+
+        if ram_size != self.ram.len() {
+            return Err(ChipError::LoadState(String::from("invalid memory size")));
+        }
+
+        let ram = reader.read_bytes(ram_size)?.to_vec();
+        let opcode = reader.read_u16()?;
+        let v = reader.read_array::<16>()?;
+        let i = reader.read_u16()?;
+        let pc = reader.read_u16()?;
+        let mut stack = [0; 16];
+
+        for addr in stack.iter_mut() {
+            *addr = reader.read_u16()?;
+        }
+
+        let sp = reader.read_u16()?;
+
+        if sp as usize > stack.len() {
+            return Err(ChipError::LoadState(String::from("invalid stack pointer")));
+        }
+
+        let delay_timer = reader.read_u8()?;
+        let sound_timer = reader.read_u8()?;
+        let interpreter_state = InterpreterState::try_from(reader.read_u8()?)?;
+        let rpl = reader.read_array::<16>()?;
+        let mut rng = ChaCha12Rng::from_seed(reader.read_array()?);
+
+        rng.set_stream(u64::from_be_bytes(reader.read_array()?));
+        rng.set_word_pos(u128::from_be_bytes(reader.read_array()?));
+
+        // Keypad
+        let key = reader.read_array::<16>()?;
+        let held_len = reader.read_u8()? as usize;
+        let held = reader.read_bytes(held_len)?;
+        let wait_key = match reader.read_u8()? {
+            0 => None,
+            _ => Some(reader.read_u8()?),
+        };
+
+        if key.iter().any(|key| *key > 1) || held.iter().chain(&wait_key).any(|key| *key > 0xf) {
+            return Err(ChipError::LoadState(String::from("invalid keypad")));
+        }
+
+        let mut keypad = KeyState::default();
+
+        keypad.update(held);
+
+        // Graphics
+        let frame_drawn = reader.read_u8()? != 0;
+        let planes = reader.read_u8()?;
+
+        if planes > 0b11 {
+            return Err(ChipError::LoadState(String::from("invalid planes")));
+        }
+
+        let w = reader.read_u16()?;
+        let h = reader.read_u16()?;
+
+        // Low resolution or SUPER-CHIP high resolution
+        if ![(RECTS_X, RECTS_Y), (RECTS_X * 2, RECTS_Y * 2)].contains(&(w as u32, h as u32)) {
+            return Err(ChipError::LoadState(String::from("invalid screen size")));
+        }
+
+        let (w, h) = (w as usize, h as usize);
+
+        let vram = Vram::from_value(w, h, reader.read_bytes(w * h)?.to_vec());
+
+        // Audio
+        let pattern = match reader.read_u8()? {
+            0 => None,
+            _ => Some(AudioPattern::new(reader.read_array()?, reader.read_u8()?)),
+        };
+
+        self.ram = ram;
+        self.opcode = opcode.into();
+        self.v = v;
+        self.i = i;
+        self.pc = ProgramCount::from(pc);
+        self.stack = stack;
+        self.sp = sp;
+        self.delay_timer = delay_timer;
+        self.sound_timer = sound_timer;
+        self.state = interpreter_state;
+        self.rpl = rpl;
+        self.rng = rng;
+        self.key = key;
+        self.keypad = keypad;
+        self.wait_key = wait_key;
+        self.frame_drawn = frame_drawn;
+        self.planes = planes;
+        self.vram = vram;
+        self.pattern = pattern;
+
+        Ok(())
+    }
+
+    fn quirks(&self) -> Quirks {
+        self.quirks
+    }
+
+    fn set_quirks(&mut self, quirks: Quirks) {
+        self.quirks = quirks;
+    }
+
+    fn keymap(&self) -> KeyMap {
+        self.keymap.clone()
+    }
+
+    fn set_keymap(&mut self, keymap: KeyMap) {
+        self.keymap = keymap;
     }
 
-    fn set_original_shift(&mut self, value: bool) {
-        self.original_shift = value;
+    fn set_seed(&mut self, seed: u64) {
+        self.rng = ChaCha12Rng::seed_from_u64(seed);
     }
 }
@@ -2,5 +2,9 @@
 pub mod interpreter;
 /// Program count state
 pub mod pc;
+/// Compatibility quirks
+pub mod quirks;
+/// Save state format
+pub mod state;
 /// Interpreter types
 pub mod types;
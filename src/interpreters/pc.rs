@@ -14,6 +14,9 @@ impl Default for ProgramCountState {
 /// Size of an operation code
 pub const OPCODE_SIZE: u16 = 2;
 
+/// Program load address
+pub const PROGRAM_START: u16 = 0x200;
+
 /// Program Count
 pub struct ProgramCount {
     /// Count
@@ -33,12 +36,12 @@ impl ProgramCount {
         self.state = state;
     }
 
-    /// Update
+    /// Update, wrapping around at the top of the memory
     pub fn step(&mut self) {
         match self.state {
-            ProgramCountState::Next => self.value += OPCODE_SIZE,
+            ProgramCountState::Next => self.value = self.value.wrapping_add(OPCODE_SIZE),
             ProgramCountState::Jump(addr) => self.value = addr,
-            ProgramCountState::Skip => self.value += OPCODE_SIZE * 2,
+            ProgramCountState::Skip => self.value = self.value.wrapping_add(OPCODE_SIZE * 2),
         }
     }
 }
@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ChipError;
+
+/// Behaviors that differ between the CHIP-8 implementations
+///
+/// The default value is the `modern` preset.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Quirks {
+    /// 8xy6 and 8xyE shift Vy into Vx, instead of shifting Vx in place
+    pub shift: bool,
+    /// Fx55 and Fx65 increment I
+    pub load: bool,
+    /// 8xy1, 8xy2 and 8xy3 reset VF to 0
+    pub vf_reset: bool,
+    /// Bnnn jumps to xnn + Vx (BXnn), instead of nnn + V0
+    pub jump: bool,
+    /// Sprites are clipped at the screen edges, instead of wrapping around
+    pub clip: bool,
+    /// Dxyn waits for the vertical blank, at most one sprite per frame
+    pub display_wait: bool,
+    /// Fx1E sets VF to 1 when I goes beyond 0xFFF
+    pub index_overflow: bool,
+    /// Fx0A completes when the key is released, instead of pressed
+    pub key_release: bool,
+}
+
+/// Named quirks presets
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuirksPreset {
+    /// COSMAC VIP, the original CHIP-8
+    Vip,
+    /// SUPER-CHIP 1.1 on the HP48
+    Schip,
+    /// XO-CHIP, as implemented by Octo
+    Xochip,
+    /// What most of the modern ROMs expect
+    #[default]
+    Modern,
+}
+
+impl FromStr for QuirksPreset {
+    type Err = ChipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let preset = match s {
+            "vip" => Self::Vip,
+            "schip" => Self::Schip,
+            "xochip" => Self::Xochip,
+            "modern" => Self::Modern,
+            _ => {
+                return Err(ChipError::UseQuirks);
+            }
+        };
+
+        Ok(preset)
+    }
+}
+
+impl From<QuirksPreset> for Quirks {
+    fn from(preset: QuirksPreset) -> Self {
+        match preset {
+            QuirksPreset::Vip => Self {
+                shift: true,
+                load: true,
+                vf_reset: true,
+                clip: true,
+                display_wait: true,
+                key_release: true,
+                ..Self::default()
+            },
+            QuirksPreset::Schip => Self {
+                jump: true,
+                clip: true,
+                ..Self::default()
+            },
+            QuirksPreset::Xochip => Self {
+                shift: true,
+                load: true,
+                ..Self::default()
+            },
+            QuirksPreset::Modern => Self::default(),
+        }
+    }
+}
+
+/// A quirks preset with individual overrides, every value is optional
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuirksConfig {
+    pub preset: Option<QuirksPreset>,
+    pub shift: Option<bool>,
+    pub load: Option<bool>,
+    pub vf_reset: Option<bool>,
+    pub jump: Option<bool>,
+    pub clip: Option<bool>,
+    pub display_wait: Option<bool>,
+    pub index_overflow: Option<bool>,
+    pub key_release: Option<bool>,
+}
+
+impl QuirksConfig {
+    /// Return true if nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Merge `other` on top of `self`
+    ///
+    /// A preset in `other` replaces the whole configuration of `self`.
+    pub fn merge(self, other: Self) -> Self {
+        if other.preset.is_some() {
+            return other;
+        }
+
+        Self {
+            preset: self.preset,
+            shift: other.shift.or(self.shift),
+            load: other.load.or(self.load),
+            vf_reset: other.vf_reset.or(self.vf_reset),
+            jump: other.jump.or(self.jump),
+            clip: other.clip.or(self.clip),
+            display_wait: other.display_wait.or(self.display_wait),
+            index_overflow: other.index_overflow.or(self.index_overflow),
+            key_release: other.key_release.or(self.key_release),
+        }
+    }
+
+    /// Return the preset quirks with the overrides
+    pub fn quirks(&self) -> Quirks {
+        let mut quirks = Quirks::from(self.preset.unwrap_or_default());
+        let overrides = [
+            (self.shift, &mut quirks.shift),
+            (self.load, &mut quirks.load),
+            (self.vf_reset, &mut quirks.vf_reset),
+            (self.jump, &mut quirks.jump),
+            (self.clip, &mut quirks.clip),
+            (self.display_wait, &mut quirks.display_wait),
+            (self.index_overflow, &mut quirks.index_overflow),
+            (self.key_release, &mut quirks.key_release),
+        ];
+
+        for (value, quirk) in overrides {
+            if let Some(value) = value {
+                *quirk = value;
+            }
+        }
+
+        quirks
+    }
+}
@@ -0,0 +1,113 @@
+use crate::error::ChipError;
+
+/// Save state magic number
+pub const STATE_MAGIC: &[u8; 4] = b"TCSS";
+/// Save state format version
+pub const STATE_VERSION: u8 = 2;
+
+/// Write the save state values (big endian)
+#[derive(Default)]
+pub struct StateWriter {
+    /// Serialized values
+    buffer: Vec<u8>,
+}
+
+impl StateWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write a byte
+    pub fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    /// Write an unsigned short int
+    pub fn write_u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// Write an unsigned int
+    pub fn write_u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// Write raw bytes
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Return the serialized state
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+/// Read the save state values (big endian)
+pub struct StateReader<'a> {
+    /// Serialized values
+    data: &'a [u8],
+    /// Read position
+    index: usize,
+}
+
+impl<'a> StateReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, index: 0 }
+    }
+
+    /// Read `n` raw bytes
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], ChipError> {
+        let end = self.index + n;
+
+        if end > self.data.len() {
+            return Err(ChipError::LoadState(String::from("truncated data")));
+        }
+
+        let bytes = &self.data[self.index..end];
+
+        self.index = end;
+        Ok(bytes)
+    }
+
+    /// Read a fixed size array
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ChipError> {
+        let mut array = [0; N];
+
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    /// Read a byte
+    pub fn read_u8(&mut self) -> Result<u8, ChipError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Read an unsigned short int
+    pub fn read_u16(&mut self) -> Result<u16, ChipError> {
+        Ok(u16::from_be_bytes(self.read_array()?))
+    }
+
+    /// Read an unsigned int
+    pub fn read_u32(&mut self) -> Result<u32, ChipError> {
+        Ok(u32::from_be_bytes(self.read_array()?))
+    }
+
+    /// Check the header, it must be the first read
+    pub fn read_header(&mut self) -> Result<(), ChipError> {
+        if &self.read_array::<4>()? != STATE_MAGIC {
+            return Err(ChipError::LoadState(String::from("not a save state")));
+        }
+
+        let version = self.read_u8()?;
+
+        if version != STATE_VERSION {
+            return Err(ChipError::LoadState(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+
+        Ok(())
+    }
+}
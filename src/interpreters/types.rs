@@ -1,13 +1,18 @@
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{error::ChipError, models::interpreter::Interpreter};
 
 use crate::interpreters::interpreter::ChipInterpreter;
 
 /// Intepreter type
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum InterpreterType {
     Original,
+    Schip,
+    Xochip,
 }
 
 impl Default for InterpreterType {
@@ -22,6 +27,8 @@ impl FromStr for InterpreterType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let api = match s {
             "original" => Self::Original,
+            "schip" => Self::Schip,
+            "xochip" => Self::Xochip,
             _ => {
                 return Err(ChipError::UseIntepreter);
             }
@@ -31,10 +38,40 @@ impl FromStr for InterpreterType {
     }
 }
 
+impl From<InterpreterType> for u8 {
+    fn from(it: InterpreterType) -> Self {
+        match it {
+            InterpreterType::Original => 0,
+            InterpreterType::Schip => 1,
+            InterpreterType::Xochip => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for InterpreterType {
+    type Error = ChipError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let it = match value {
+            0 => Self::Original,
+            1 => Self::Schip,
+            2 => Self::Xochip,
+            _ => {
+                return Err(ChipError::UseIntepreter);
+            }
+        };
+
+        Ok(it)
+    }
+}
+
 impl From<InterpreterType> for Box<dyn Interpreter> {
     fn from(it: InterpreterType) -> Self {
         match it {
             InterpreterType::Original => Box::new(ChipInterpreter::new()),
+            InterpreterType::Schip | InterpreterType::Xochip => {
+                Box::new(ChipInterpreter::with_type(it))
+            }
         }
     }
 }
@@ -0,0 +1,202 @@
+use std::{collections::HashMap, fs, path::Path, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ChipError,
+    event::{GamepadButton, Hotkey, Input},
+};
+
+/// Keyboard layouts presets
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyMapPreset {
+    Qwerty,
+    #[default]
+    Azerty,
+    Dvorak,
+}
+
+impl FromStr for KeyMapPreset {
+    type Err = ChipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let preset = match s {
+            "qwerty" => Self::Qwerty,
+            "azerty" => Self::Azerty,
+            "dvorak" => Self::Dvorak,
+            _ => {
+                return Err(ChipError::UseKeyMap(s.to_string()));
+            }
+        };
+
+        Ok(preset)
+    }
+}
+
+/// Keys of the CHIP-8 hex keypad, in the keypad order
+///
+/// ```text
+/// 1 2 3 C
+/// 4 5 6 D
+/// 7 8 9 E
+/// A 0 B F
+/// ```
+const KEYPAD: [u8; 16] = [
+    0x1, 0x2, 0x3, 0xc, 0x4, 0x5, 0x6, 0xd, 0x7, 0x8, 0x9, 0xe, 0xa, 0x0, 0xb, 0xf,
+];
+
+impl KeyMapPreset {
+    /// Return the keyboard keys, in the keypad order
+    fn layout(&self) -> [Hotkey; 16] {
+        use Hotkey::*;
+
+        match self {
+            Self::Qwerty => [Num1, Num2, Num3, Num4, Q, W, E, R, A, S, D, F, Z, X, C, V],
+            Self::Azerty => [Num1, Num2, Num3, Num4, A, Z, E, R, Q, S, D, F, W, X, C, V],
+            Self::Dvorak => [
+                Num1, Num2, Num3, Num4, Quote, Comma, Period, P, A, O, E, U, Semicolon, Q, J, K,
+            ],
+        }
+    }
+}
+
+/// A CHIP-8 keypad key (0 - F), written as an hexadecimal string
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeypadKey(pub u8);
+
+impl TryFrom<String> for KeypadKey {
+    type Error = ChipError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match u8::from_str_radix(value.trim_start_matches("0x"), 16) {
+            Ok(key) if key < 16 => Ok(Self(key)),
+            _ => Err(ChipError::UseKeyMap(value)),
+        }
+    }
+}
+
+/// A keyboard layout preset with extra keys and gamepad buttons, every value is optional
+///
+/// JSON form : `{ "preset": "qwerty", "keys": { "Up": "5" }, "buttons": { "A": "6" } }`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyMapConfig {
+    /// Base layout
+    pub preset: Option<KeyMapPreset>,
+    /// Extra keyboard keys, on top of the preset
+    pub keys: HashMap<Hotkey, KeypadKey>,
+    /// Gamepad buttons, on top of the default bindings
+    pub buttons: HashMap<GamepadButton, KeypadKey>,
+}
+
+impl KeyMapConfig {
+    /// Read a JSON file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ChipError> {
+        let json = fs::read_to_string(path).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+
+        serde_json::from_str(&json).map_err(|e| ChipError::UseKeyMap(e.to_string()))
+    }
+
+    /// Return true if nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Merge `other` on top of `self`, its preset and its keys win
+    pub fn merge(mut self, other: Self) -> Self {
+        self.keys.extend(other.keys);
+        self.buttons.extend(other.buttons);
+
+        Self {
+            preset: other.preset.or(self.preset),
+            keys: self.keys,
+            buttons: self.buttons,
+        }
+    }
+}
+
+/// Default gamepad bindings, the D-pad is on the 5 7 8 9 cross
+const BUTTONS: [(GamepadButton, u8); 8] = [
+    (GamepadButton::DPadUp, 0x5),
+    (GamepadButton::DPadLeft, 0x7),
+    (GamepadButton::DPadDown, 0x8),
+    (GamepadButton::DPadRight, 0x9),
+    (GamepadButton::A, 0x6),
+    (GamepadButton::B, 0x4),
+    (GamepadButton::X, 0xa),
+    (GamepadButton::Y, 0xb),
+];
+
+/// Translate the keyboard keys and gamepad buttons into the CHIP-8 keypad keys
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeyMap {
+    keys: HashMap<Hotkey, u8>,
+    buttons: HashMap<GamepadButton, u8>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::from(KeyMapPreset::default())
+    }
+}
+
+impl From<KeyMapPreset> for KeyMap {
+    fn from(preset: KeyMapPreset) -> Self {
+        let mut keys: HashMap<Hotkey, u8> = preset.layout().into_iter().zip(KEYPAD).collect();
+
+        // The first row is also on the numeric keypad
+        keys.extend([
+            (Hotkey::Kp1, 0x1),
+            (Hotkey::Kp2, 0x2),
+            (Hotkey::Kp3, 0x3),
+            (Hotkey::Kp4, 0xc),
+        ]);
+
+        Self {
+            keys,
+            buttons: BUTTONS.into_iter().collect(),
+        }
+    }
+}
+
+impl From<&KeyMapConfig> for KeyMap {
+    fn from(config: &KeyMapConfig) -> Self {
+        let mut keymap = Self::from(config.preset.unwrap_or_default());
+
+        for (hotkey, key) in config.keys.iter() {
+            keymap.keys.insert(*hotkey, key.0);
+        }
+        for (button, key) in config.buttons.iter() {
+            keymap.buttons.insert(*button, key.0);
+        }
+
+        keymap
+    }
+}
+
+impl KeyMap {
+    /// Return the keypad key of a keyboard key
+    pub fn key(&self, hotkey: Hotkey) -> Option<u8> {
+        self.keys.get(&hotkey).copied()
+    }
+
+    /// Return the keypad key of a gamepad button
+    pub fn button(&self, button: GamepadButton) -> Option<u8> {
+        self.buttons.get(&button).copied()
+    }
+
+    /// Return the keypad keys of the inputs
+    pub fn keys(&self, inputs: &[Input]) -> Vec<usize> {
+        inputs
+            .iter()
+            .filter_map(|input| match input {
+                Input::Hotkey(hotkey) => self.key(*hotkey),
+                Input::Gamepad(button) => self.button(*button),
+                Input::Mouse(_) => None,
+            })
+            .map(|key| key as usize)
+            .collect()
+    }
+}
@@ -1,14 +1,34 @@
 /// Api API(s) implementations
 pub mod apis;
+/// Assembler
+pub mod assembler;
+/// ROM database
+pub mod database;
+/// Interactive debugger
+pub mod debugger;
+/// Disassembler
+pub mod disassembler;
 /// Emulator
 pub mod emulator;
 /// Errors
 pub mod error;
 /// key events
 pub mod event;
+/// Golden-image test harness
+pub mod harness;
 /// Interpreters implementations
 pub mod interpreters;
+/// Keyboard mapping
+pub mod keymap;
 /// Public traits
 pub mod models;
+/// Input recording and replay
+pub mod movie;
 /// Global structs that are used almost everywhere
 pub mod properties;
+/// Screen export to image files
+pub mod screenshot;
+/// Messages about the emulator
+pub mod status;
+/// Gameplay video recording
+pub mod video;
@@ -1,41 +1,154 @@
-use std::path::PathBuf;
-use structopt::StructOpt;
+use std::{fs, path::PathBuf, time::Duration};
+use structopt::{
+    clap::{Error, ErrorKind},
+    StructOpt,
+};
 
 use tinychip::{
     apis::api::{ApiKind, RECTS_X, RECTS_Y},
+    assembler::Assembler,
+    database::{RomDatabase, RomSettings},
+    disassembler::Disassembler,
     emulator::EmulatorBuilder,
     error::ChipError,
-    interpreters::types::InterpreterType,
-    models::{core::Core, interpreter::Interpreter},
+    interpreters::{
+        quirks::{QuirksConfig, QuirksPreset},
+        types::InterpreterType,
+    },
+    keymap::{KeyMapConfig, KeyMapPreset},
+    models::core::Core,
+    movie::Movie,
+    properties::{
+        color::Color,
+        palette::{Palette, PaletteTheme},
+        scaling::ScaleMode,
+    },
 };
 
+#[derive(StructOpt, Debug)]
+enum Command {
+    /// Assemble a mnemonic source into a ROM
+    Asm {
+        /// Source file
+        #[structopt(parse(from_os_str))]
+        source: PathBuf,
+        /// Interpreter, value(s): original, schip, xochip
+        #[structopt(long)]
+        interpreter: Option<InterpreterType>,
+        /// Output file
+        #[structopt(short, long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Disassemble a ROM
+    Disasm {
+        /// Input file
+        #[structopt(parse(from_os_str))]
+        rom: PathBuf,
+        /// Interpreter, value(s): original, schip, xochip
+        #[structopt(long)]
+        interpreter: Option<InterpreterType>,
+        /// Output file, stdout by default
+        #[structopt(short, long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+}
+
 #[derive(StructOpt, Debug)]
 #[structopt(name = "tinychip")]
 struct Opt {
-    /// Input file
+    #[structopt(subcommand)]
+    command: Option<Command>,
+    /// Input file, required without subcommand
     #[structopt(parse(from_os_str))]
-    rom: PathBuf,
+    rom: Option<PathBuf>,
     /// Window width
     #[structopt(short, long)]
     width: Option<u32>,
     /// Window height
     #[structopt(short, long)]
     height: Option<u32>,
-    /// Graphical API, value(s): sfml, sdl
+    /// Graphical API, value(s): sdl, sfml, headless (depends on the cargo features)
     #[structopt(long)]
     api: Option<ApiKind>,
-    /// Interpreter, value(s): original
+    /// Interpreter, value(s): original, schip, xochip
     #[structopt(long)]
     interpreter: Option<InterpreterType>,
-    /// Cycle(s) per second (Hz)
+    /// Cycle(s) per second (Hz), between 500 and 30000
     #[structopt(long)]
     cycles: Option<u64>,
-    /// use the original semantic for fx55, fx65
+    /// Quirks preset, value(s): vip, schip, xochip, modern
+    #[structopt(long)]
+    quirks: Option<QuirksPreset>,
+    /// use the original semantic for fx55, fx65 (I is incremented)
     #[structopt(long)]
     original_load: Option<bool>,
-    /// use the original semantic for 8xy6, 8xye
+    /// use the original semantic for 8xy6, 8xye (Vy is shifted)
     #[structopt(long)]
     original_shift: Option<bool>,
+    /// 8xy1, 8xy2, 8xy3 reset VF
+    #[structopt(long)]
+    vf_reset: Option<bool>,
+    /// Bnnn jumps to xnn + Vx
+    #[structopt(long)]
+    jump_vx: Option<bool>,
+    /// Clip the sprites at the screen edges instead of wrapping
+    #[structopt(long)]
+    clip: Option<bool>,
+    /// Draw at most one sprite per frame
+    #[structopt(long)]
+    display_wait: Option<bool>,
+    /// Fx1E sets VF when I goes beyond 0xfff
+    #[structopt(long)]
+    index_overflow: Option<bool>,
+    /// Fx0A waits for the key release
+    #[structopt(long)]
+    key_release: Option<bool>,
+    /// Stop after n frame(s) (60 per second), headless only
+    #[structopt(long)]
+    max_frames: Option<u64>,
+    /// Stop after n second(s), headless only
+    #[structopt(long)]
+    timeout: Option<u64>,
+    /// Keyboard mapping, value(s): qwerty, azerty, dvorak or a JSON file
+    #[structopt(long)]
+    keymap: Option<String>,
+    /// Palette theme, value(s): default, amber, green, lcd, octo
+    #[structopt(long)]
+    theme: Option<PaletteTheme>,
+    /// Foreground color, hexadecimal (#rrggbb)
+    #[structopt(long)]
+    fg: Option<Color>,
+    /// Background color, hexadecimal (#rrggbb)
+    #[structopt(long)]
+    bg: Option<Color>,
+    /// Screen scaling, value(s): integer, fit, stretch
+    #[structopt(long)]
+    scale: Option<ScaleMode>,
+    /// Phosphor persistence, part of the previous color kept at every frame (0 to 1)
+    #[structopt(long)]
+    phosphor: Option<f32>,
+    /// ROM database JSON file, its entries win over the bundled ones
+    #[structopt(long, parse(from_os_str))]
+    database: Option<PathBuf>,
+    /// Start paused into the interactive debugger (terminal)
+    #[structopt(long)]
+    debug: bool,
+    /// Start in fullscreen (F11 to switch)
+    #[structopt(long)]
+    fullscreen: bool,
+    /// Seed of the random numbers generator, the runs become reproducible
+    #[structopt(long)]
+    seed: Option<u64>,
+    /// Record the inputs into a movie file
+    #[structopt(long, parse(from_os_str), conflicts_with = "replay")]
+    record: Option<PathBuf>,
+    /// Replay the inputs of a movie file, for the same ROM. Its interpreter, quirks, clock,
+    /// seed and keymap win over the other options and the ROM database
+    #[structopt(long, parse(from_os_str))]
+    replay: Option<PathBuf>,
+    /// Record the gameplay into an animated GIF file
+    #[structopt(long, parse(from_os_str))]
+    record_video: Option<PathBuf>,
 }
 
 impl Opt {
@@ -59,52 +172,177 @@ impl Opt {
         self.api.unwrap_or_default()
     }
 
-    /// Return the interpreter
-    pub fn interpreter(&self) -> Box<dyn Interpreter> {
-        self.interpreter.unwrap_or_default().into()
+    /// Return the cycles per second, between 500 and 30000 like the ROM database clocks
+    pub fn cycles(&self) -> Option<u64> {
+        self.cycles.map(|cycles| cycles.clamp(500, 30000))
+    }
+
+    /// Return the quirks preset with the overrides
+    pub fn quirks(&self) -> QuirksConfig {
+        QuirksConfig {
+            preset: self.quirks,
+            shift: self.original_shift,
+            load: self.original_load,
+            vf_reset: self.vf_reset,
+            jump: self.jump_vx,
+            clip: self.clip,
+            display_wait: self.display_wait,
+            index_overflow: self.index_overflow,
+            key_release: self.key_release,
+        }
+    }
+
+    /// Return the keyboard mapping, a preset name or a JSON file
+    pub fn keymap(&self) -> Result<KeyMapConfig, ChipError> {
+        let keymap = match &self.keymap {
+            None => KeyMapConfig::default(),
+            Some(value) => match value.parse::<KeyMapPreset>() {
+                Ok(preset) => KeyMapConfig {
+                    preset: Some(preset),
+                    ..KeyMapConfig::default()
+                },
+                Err(_) => KeyMapConfig::from_file(value)?,
+            },
+        };
+
+        Ok(keymap)
     }
 
-    /// Return the interpreter
-    pub fn cycles(&self) -> u64 {
-        let ret = self.cycles.unwrap_or(500);
+    /// Return the palette, if any color option is given
+    pub fn palette(&self) -> Option<Palette> {
+        if self.theme.is_none() && self.fg.is_none() && self.bg.is_none() {
+            return None;
+        }
+
+        let mut palette = Palette::from(self.theme.unwrap_or_default());
 
-        if ret <= 500 {
-            500
-        } else if ret > 2000 {
-            2000
-        } else {
-            ret
+        if let Some(color) = self.fg {
+            palette = palette.with_foreground(color);
+        }
+        if let Some(color) = self.bg {
+            palette = palette.with_background(color);
         }
+
+        Some(palette)
     }
 
-    /// Return original load state
-    pub fn original_load(&self) -> bool {
-        self.original_load.unwrap_or(false)
+    /// Return the settings given on the command line, they win over the ROM database
+    pub fn settings(&self) -> Result<RomSettings, ChipError> {
+        Ok(RomSettings {
+            interpreter: self.interpreter,
+            quirks: self.quirks(),
+            clock: self.cycles(),
+            keymap: self.keymap()?,
+            colors: self.palette().map(|palette| palette.colors.to_vec()),
+            ..RomSettings::default()
+        })
     }
 
-    /// Return original shift state
-    pub fn original_shift(&self) -> bool {
-        self.original_shift.unwrap_or(false)
+    /// Return the bundled ROM database, extended by the user one
+    pub fn database(&self) -> Result<RomDatabase, ChipError> {
+        let mut database = RomDatabase::bundled();
+
+        if let Some(path) = &self.database {
+            database.extend(RomDatabase::from_file(path)?);
+        }
+
+        Ok(database)
+    }
+}
+
+/// Run a subcommand
+fn run_command(command: Command) -> Result<(), ChipError> {
+    match command {
+        Command::Asm {
+            source,
+            interpreter,
+            output,
+        } => {
+            let source =
+                fs::read_to_string(source).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+            let program = Assembler::new(interpreter.unwrap_or_default()).assemble(&source)?;
+
+            fs::write(output, program).map_err(|e| ChipError::WriteFile(e.to_string()))
+        }
+        Command::Disasm {
+            rom,
+            interpreter,
+            output,
+        } => {
+            let program = fs::read(rom).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+            let listing = Disassembler::new(interpreter.unwrap_or_default()).listing(&program);
+
+            match output {
+                Some(path) => {
+                    fs::write(path, listing).map_err(|e| ChipError::WriteFile(e.to_string()))
+                }
+                None => {
+                    print!("{}", listing);
+
+                    Ok(())
+                }
+            }
+        }
     }
 }
 
 fn main() -> Result<(), ChipError> {
     let args = Opt::from_args();
 
-    let mut interpreter = args.interpreter();
+    if let Some(command) = args.command {
+        return run_command(command);
+    }
 
-    interpreter.set_original_load(args.original_load());
-    interpreter.set_original_shift(args.original_shift());
+    let rom = match args.rom.clone() {
+        Some(rom) => rom,
+        None => Error::with_description(
+            "The following required arguments were not provided:\n    <rom>",
+            ErrorKind::MissingRequiredArgument,
+        )
+        .exit(),
+    };
 
-    let mut emu = EmulatorBuilder::new()
+    let mut builder = EmulatorBuilder::new()
         .set_api(args.api())
         .set_window_size(args.size())
         .set_window_title("tinychip")
-        .set_interpreter(interpreter)
-        .set_clock(args.cycles())
-        .build();
+        .set_database(args.database()?)
+        .set_settings(args.settings()?)
+        .set_debug(args.debug)
+        .set_fullscreen(args.fullscreen)
+        .set_status_callback(|status| match status.is_error() {
+            true => eprintln!("{}", status),
+            false => println!("{}", status),
+        });
+
+    if let Some(seed) = args.seed {
+        builder = builder.set_seed(seed);
+    }
+    if let Some(mode) = args.scale {
+        builder = builder.set_scale_mode(mode);
+    }
+    if let Some(decay) = args.phosphor {
+        builder = builder.set_phosphor(decay);
+    }
+    if let Some(path) = &args.record {
+        builder = builder.set_record(path);
+    }
+    if let Some(path) = &args.replay {
+        builder = builder.set_replay(Movie::from_file(path)?);
+    }
+    if let Some(path) = &args.record_video {
+        builder = builder.set_video_record(path);
+    }
+    if let Some(frames) = args.max_frames {
+        builder = builder.set_max_frames(frames);
+    }
+    if let Some(seconds) = args.timeout {
+        builder = builder.set_timeout(Duration::from_secs(seconds));
+    }
+
+    let mut emu = builder.build();
 
-    emu.load_from_file(args.rom)?;
+    emu.load_from_file(rom)?;
     emu.run();
 
     Ok(())
@@ -1,6 +1,11 @@
-use crate::properties::{color::Color, rectangle::Rectangle};
+use std::any::Any;
 
-use crate::event::Input;
+use crate::{
+    error::ChipError,
+    properties::{color::Color, rectangle::Rectangle},
+};
+
+use crate::event::{Input, KeyEvent};
 
 use super::audio::Audio;
 
@@ -9,12 +14,47 @@ pub trait Api: Audio {
     fn clear(&mut self);
     /// Draw a rectangle at the screen
     fn draw_rect(&mut self, rect: Rectangle, color: Color);
+    /// Draw the whole screen at once, `pixels` are the colors of the
+    /// `size.0 * size.1` pixels row by row, stretched over `area`
+    ///
+    /// By default a rectangle is drawn per pixel, the backends can upload
+    /// the pixels into a texture instead.
+    fn draw_frame(
+        &mut self,
+        size: (usize, usize),
+        pixels: &[Color],
+        area: Rectangle,
+    ) -> Result<(), ChipError> {
+        let (w, h) = (size.0 as i64, size.1 as i64);
+        // Pixel edge on the screen, the pixels can have different sizes
+        let x_edge = |x: i64| area.x + (x * area.w as i64 / w) as i32;
+        let y_edge = |y: i64| area.y + (y * area.h as i64 / h) as i32;
+
+        for (i, color) in pixels.iter().enumerate() {
+            let (x, y) = (i as i64 % w, i as i64 / w);
+            let (x_start, y_start) = (x_edge(x), y_edge(y));
+            let rect_w = (x_edge(x + 1) - x_start) as u32;
+            let rect_h = (y_edge(y + 1) - y_start) as u32;
+
+            self.draw_rect(Rectangle::from((x_start, y_start, rect_w, rect_h)), *color);
+        }
+
+        Ok(())
+    }
     /// Return the window state
     fn is_window_open(&self) -> bool;
     /// Display the drawing on the screen as a complete picture
     fn display(&mut self);
-    /// Return the pressed inputs
-    fn events(&mut self) -> Vec<Input>;
+    /// Return the press and release edges of the inputs, since the previous call
+    fn events(&mut self) -> Vec<KeyEvent<Input>>;
     /// Return the window size (width, height)
     fn window_size(&self) -> (u32, u32);
+    /// Resize the window, in windowed mode
+    fn set_window_size(&mut self, size: (u32, u32));
+    /// Switch between the fullscreen and windowed modes
+    fn set_fullscreen(&mut self, fullscreen: bool);
+    /// Set the window title
+    fn set_title(&mut self, title: &str);
+    /// Return the backend as `Any`, to reach its own methods
+    fn as_any(&self) -> &dyn Any;
 }
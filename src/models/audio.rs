@@ -1,7 +1,11 @@
+use crate::properties::pattern::AudioPattern;
+
 /// Audio controller
 pub trait Audio {
     /// Resume the beep sound
     fn resume_beep(&mut self);
     /// Pause the beep sound
     fn pause_beep(&mut self);
+    /// Play the beep sound as an audio pattern instead of the default tone
+    fn set_pattern(&mut self, pattern: AudioPattern);
 }
@@ -94,7 +94,7 @@ pub trait Instructions {
     ///
     /// Set Vx = Vx - Vy, set VF = NOT borrow.
     ///
-    /// If Vx > Vy, then VF is set to 1, otherwise 0. Then Vy is subtracted from Vx, and the results stored in Vx.
+    /// If Vx >= Vy, then VF is set to 1, otherwise 0. Then Vy is subtracted from Vx, and the results stored in Vx.
     fn sub_vx_vy(&mut self);
     /// 8xy6 - SHR Vx {, Vy}
     ///
@@ -106,13 +106,13 @@ pub trait Instructions {
     ///
     /// Set Vx = Vy SHR 1.
     ///
-    /// If the least-significant bit of Vx is 1, then VF is set to 1, otherwise 0. Then yx is divided by 2.
+    /// If the least-significant bit of Vy is 1, then VF is set to 1, otherwise 0. Then Vy is divided by 2, and the result stored in Vx.
     fn shr_vx_vy_original(&mut self);
     /// 8xy7 - SUBN Vx, Vy
     ///
     /// Set Vx = Vy - Vx, set VF = NOT borrow.
     ///
-    /// If Vy > Vx, then VF is set to 1, otherwise 0. Then Vx is subtracted from Vy, and the results stored in Vx.
+    /// If Vy >= Vx, then VF is set to 1, otherwise 0. Then Vx is subtracted from Vy, and the results stored in Vx.
     fn subn_vx_vy(&mut self);
     /// 8xyE - SHL Vx {, Vy}
     ///
@@ -124,7 +124,7 @@ pub trait Instructions {
     ///
     /// Set Vx = Vy SHL 1.
     ///
-    /// If the most-significant bit of Vx is 1, then VF is set to 1, otherwise to 0. Then Vy is multiplied by 2.
+    /// If the most-significant bit of Vy is 1, then VF is set to 1, otherwise to 0. Then Vy is multiplied by 2, and the result stored in Vx.
     fn shl_vx_vy_original(&mut self);
     /// 9xy0 - SNE Vx, Vy
     ///
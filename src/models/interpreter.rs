@@ -1,24 +1,48 @@
 use crate::{
-    event::Input,
+    error::ChipError,
+    event::{Input, KeyEvent},
+    interpreters::{quirks::Quirks, types::InterpreterType},
+    keymap::KeyMap,
     models::{instructions::Instructions, memory::Memory},
-    properties::vram::Vram,
+    properties::{pattern::AudioPattern, registers::Registers, vram::Vram},
 };
 
 /// Parse and interprets the raw byte instructions
 pub trait Interpreter: Memory + Instructions {
     /// Returns the graphic screen memory
-    fn vram(&self) -> Vram;
+    fn vram(&self) -> &Vram;
+    /// Returns a snapshot of the registers
+    fn registers(&self) -> Registers;
+    /// Returns the interpreter type
+    fn kind(&self) -> InterpreterType;
     /// It represents a tick (we can consider its a
     /// cycle assuming every instruction take only one cycle)
     ///
-    /// If the return value is true then display the screen
-    fn step(&mut self, inputs: Vec<Input>) -> bool;
-    /// Load the program into the memory
-    fn load_program(&mut self, program: Vec<u8>);
+    /// The press and release edges of the inputs are given once per frame,
+    /// at its first cycle. If the return value is true then display the screen
+    fn step(&mut self, events: &[KeyEvent<Input>]) -> bool;
+    /// Decrement the delay and sound timers, once per frame (60hz)
+    fn timers_tick(&mut self);
+    /// Load the program into the memory, it must fit after the interpreter area
+    fn load_program(&mut self, program: Vec<u8>) -> Result<(), ChipError>;
     /// Return if it has to beep
     fn beep(&self) -> bool;
-    /// Force to use the original load semantic
-    fn set_original_load(&mut self, value: bool);
-    /// Force to use the original load semantic
-    fn set_original_shift(&mut self, value: bool);
+    /// Return the audio pattern, if the program has set one
+    fn audio_pattern(&self) -> Option<AudioPattern>;
+    /// Return if the program asked to exit
+    fn exited(&self) -> bool;
+    /// Serialize the whole interpreter state
+    fn save_state(&self) -> Vec<u8>;
+    /// Restore a state serialized by `save_state`, for the same program
+    fn load_state(&mut self, state: &[u8]) -> Result<(), ChipError>;
+    /// Return the compatibility behaviors
+    fn quirks(&self) -> Quirks;
+    /// Set the compatibility behaviors
+    fn set_quirks(&mut self, quirks: Quirks);
+    /// Return the keyboard to keypad mapping
+    fn keymap(&self) -> KeyMap;
+    /// Set the keyboard to keypad mapping
+    fn set_keymap(&mut self, keymap: KeyMap);
+    /// Seed the random numbers generator, to make the runs reproducible
+    fn set_seed(&mut self, seed: u64);
 }
@@ -12,3 +12,7 @@ pub mod instructions;
 pub mod interpreter;
 /// Used for manipulate a memory buffer, write, read, etc..
 pub mod memory;
+/// SUPER-CHIP instructions
+pub mod super_instructions;
+/// XO-CHIP instructions
+pub mod xo_instructions;
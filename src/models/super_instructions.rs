@@ -0,0 +1,47 @@
+/// SUPER-CHIP 1.1 instructions (10)
+///
+/// Descriptions sources : http://devernay.free.fr/hacks/chip8/C8TECH10.HTM#3.2
+pub trait SuperInstructions {
+    /// 00Cn - SCD nibble
+    ///
+    /// Scroll display n lines down.
+    fn scd_n(&mut self);
+    /// 00FB - SCR
+    ///
+    /// Scroll display 4 pixels right.
+    fn scr(&mut self);
+    /// 00FC - SCL
+    ///
+    /// Scroll display 4 pixels left.
+    fn scl(&mut self);
+    /// 00FD - EXIT
+    ///
+    /// Exit the interpreter.
+    fn exit(&mut self);
+    /// 00FE - LOW
+    ///
+    /// Disable extended screen mode (64x32).
+    fn low(&mut self);
+    /// 00FF - HIGH
+    ///
+    /// Enable extended screen mode (128x64).
+    fn high(&mut self);
+    /// Dxy0 - DRW Vx, Vy, 0
+    ///
+    /// Draw a 16x16 sprite starting at memory location I at (Vx, Vy), set VF = collision.
+    ///
+    /// Each row of the sprite is made of 2 bytes, so it reads 32 bytes from memory.
+    fn drw_vx_vy_0(&mut self);
+    /// Fx30 - LD HF, Vx
+    ///
+    /// Set I = location of the 8x10 sprite for digit Vx.
+    fn ld_hf_vx(&mut self);
+    /// Fx75 - LD R, Vx
+    ///
+    /// Store V0 through Vx in the RPL user flags (x <= 7).
+    fn ld_r_vx(&mut self);
+    /// Fx85 - LD Vx, R
+    ///
+    /// Read V0 through Vx from the RPL user flags (x <= 7).
+    fn ld_vx_r(&mut self);
+}
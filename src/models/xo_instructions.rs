@@ -0,0 +1,41 @@
+/// XO-CHIP instructions (7)
+///
+/// Descriptions sources : https://johnearnest.github.io/Octo/docs/XO-ChipSpecification.html
+pub trait XoInstructions {
+    /// 00Dn - SCU nibble
+    ///
+    /// Scroll the selected planes n lines up.
+    fn scu_n(&mut self);
+    /// 5xy2 - LD [I], Vx-Vy
+    ///
+    /// Store registers Vx through Vy in memory starting at location I.
+    ///
+    /// The registers are stored in reverse order if x > y, I is not modified.
+    fn ld_i_vx_vy(&mut self);
+    /// 5xy3 - LD Vx-Vy, [I]
+    ///
+    /// Read registers Vx through Vy from memory starting at location I.
+    ///
+    /// The registers are read in reverse order if x > y, I is not modified.
+    fn ld_vx_vy_i(&mut self);
+    /// F000 nnnn - LD I, long
+    ///
+    /// Set I = nnnn.
+    ///
+    /// The 16 bits address is read from the next 2 bytes, this instruction is 4 bytes long.
+    fn ld_i_long(&mut self);
+    /// Fn01 - PLANE n
+    ///
+    /// Select the drawing planes with the bitmask n.
+    ///
+    /// The drawing, clearing and scrolling instructions only affect the selected planes.
+    fn plane_n(&mut self);
+    /// F002 - AUDIO
+    ///
+    /// Store 16 bytes starting at location I in the audio pattern buffer.
+    fn audio(&mut self);
+    /// Fx3A - PITCH Vx
+    ///
+    /// Set the audio pattern playback pitch = Vx.
+    fn pitch_vx(&mut self);
+}
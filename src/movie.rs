@@ -0,0 +1,98 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::ChipError,
+    event::{Input, KeyEvent, KeyState},
+    interpreters::{quirks::Quirks, types::InterpreterType},
+    keymap::KeyMap,
+};
+
+/// Everything needed to reproduce a session, besides the inputs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MovieHeader {
+    /// SHA-1 of the ROM, as in the ROM database
+    pub rom: String,
+    /// Interpreter type
+    pub interpreter: InterpreterType,
+    /// Seed of the random numbers generator
+    pub seed: u64,
+    /// Cycles per second (hz)
+    pub clock: u64,
+    /// Compatibility behaviors
+    pub quirks: Quirks,
+    /// Keyboard to keypad mapping, the inputs are stored as keyboard keys
+    pub keymap: KeyMap,
+    /// Number of recorded frames
+    pub frames: u64,
+}
+
+/// Input edges of the frames, only the frames with edges are stored
+pub type MovieEvents = Vec<(u64, Vec<KeyEvent<Input>>)>;
+
+/// Input edges of every frame given to the interpreter, stored as a JSON file (`.c8m`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Movie {
+    pub header: MovieHeader,
+    /// Press and release edges, by frame
+    pub events: MovieEvents,
+}
+
+impl Movie {
+    pub fn new(header: MovieHeader) -> Self {
+        Self {
+            header,
+            events: MovieEvents::new(),
+        }
+    }
+
+    /// Read a movie file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ChipError> {
+        let json = fs::read_to_string(path).map_err(|e| ChipError::ReadFile(e.to_string()))?;
+
+        serde_json::from_str(&json).map_err(|e| ChipError::LoadMovie(e.to_string()))
+    }
+
+    /// Write the movie into a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ChipError> {
+        let json = serde_json::to_string(self).map_err(|e| ChipError::WriteFile(e.to_string()))?;
+
+        fs::write(path, json).map_err(|e| ChipError::WriteFile(e.to_string()))
+    }
+
+    /// Record the input edges of a frame, the frames must be pushed in order
+    pub fn push(&mut self, frame: u64, events: &[KeyEvent<Input>]) {
+        if !events.is_empty() {
+            self.events.push((frame, events.to_vec()));
+        }
+        self.header.frames = frame + 1;
+    }
+
+    /// Return the input edges of a frame, `None` after the end of the movie
+    pub fn events(&self, frame: u64) -> Option<Vec<KeyEvent<Input>>> {
+        if frame >= self.header.frames {
+            return None;
+        }
+
+        let events = match self
+            .events
+            .binary_search_by_key(&frame, |(start, _)| *start)
+        {
+            Ok(index) => self.events[index].1.clone(),
+            Err(_) => Vec::new(),
+        };
+
+        Some(events)
+    }
+
+    /// Return the inputs still held at the end of the movie
+    pub fn held(&self) -> Vec<Input> {
+        let mut keys = KeyState::default();
+
+        for (_, events) in &self.events {
+            keys.apply(events);
+        }
+        keys.held().to_vec()
+    }
+}
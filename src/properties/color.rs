@@ -1,5 +1,12 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::error::ChipError;
+
 /// The unique Color model for this crate
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
 pub struct Color {
     /// Red
     pub r: u8,
@@ -56,3 +63,34 @@ impl From<(u8, u8, u8)> for Color {
         Self { r, g, b, a: 255 }
     }
 }
+
+/// Parse an hexadecimal color, `#rrggbb` or `#rrggbbaa` (`#` is optional)
+impl FromStr for Color {
+    type Err = ChipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim_start_matches('#');
+        let error = || ChipError::ParseColor(s.to_string());
+
+        if hex.len() != 6 && hex.len() != 8 {
+            return Err(error());
+        }
+
+        let channel = |index: usize| {
+            hex.get(index..index + 2)
+                .and_then(|value| u8::from_str_radix(value, 16).ok())
+                .ok_or_else(error)
+        };
+        let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+
+        Ok(Self::from((channel(0)?, channel(2)?, channel(4)?, alpha)))
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = ChipError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
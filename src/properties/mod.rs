@@ -4,7 +4,17 @@ pub mod clock;
 pub mod color;
 /// Opcode
 pub mod opcode;
+/// Pixel colors
+pub mod palette;
+/// XO-CHIP audio pattern
+pub mod pattern;
+/// Phosphor persistence filter
+pub mod phosphor;
 /// Rectangle
 pub mod rectangle;
+/// Registers snapshot
+pub mod registers;
+/// Screen scaling modes
+pub mod scaling;
 /// Vram
 pub mod vram;
@@ -1,4 +1,34 @@
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
+
+use crate::interpreters::types::InterpreterType;
+
+/// Assembly form of an opcode
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mnemonic {
+    /// Instruction name
+    pub name: &'static str,
+    /// Comma separated operands
+    pub operands: String,
+}
+
+impl Mnemonic {
+    pub fn new<S: Into<String>>(name: &'static str, operands: S) -> Self {
+        Self {
+            name,
+            operands: operands.into(),
+        }
+    }
+}
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.operands.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} {}", self.name, self.operands)
+        }
+    }
+}
 
 /// Contains some methods to extract arguments, etc..
 #[derive(Clone, Copy)]
@@ -38,6 +68,78 @@ impl Opcode {
     pub fn kk(&self) -> u8 {
         (self.value & 0x00ff) as u8
     }
+
+    /// Return true if it is the 4 bytes XO-CHIP `F000 nnnn`
+    pub fn is_long(&self, kind: InterpreterType) -> bool {
+        kind == InterpreterType::Xochip && self.value == 0xf000
+    }
+
+    /// Decode the opcode for the interpreter `kind`
+    ///
+    /// `next` is the following 2 bytes, only used by `F000 nnnn`
+    pub fn mnemonic(&self, kind: InterpreterType, next: u16) -> Mnemonic {
+        let is_super = kind != InterpreterType::Original;
+        let is_xo = kind == InterpreterType::Xochip;
+        let (x, y) = (self.x(), self.y());
+        let (n, kk, nnn) = (self.n(), self.kk(), self.nnn());
+
+        match (*self).into() {
+            (0x00, 0x00, 0x0e, 0x00) => Mnemonic::new("CLS", ""),
+            (0x00, 0x00, 0x0e, 0x0e) => Mnemonic::new("RET", ""),
+            (0x00, 0x00, 0x0c, _) if is_super => Mnemonic::new("SCD", format!("{}", n)),
+            (0x00, 0x00, 0x0d, _) if is_xo => Mnemonic::new("SCU", format!("{}", n)),
+            (0x00, 0x00, 0x0f, 0x0b) if is_super => Mnemonic::new("SCR", ""),
+            (0x00, 0x00, 0x0f, 0x0c) if is_super => Mnemonic::new("SCL", ""),
+            (0x00, 0x00, 0x0f, 0x0d) if is_super => Mnemonic::new("EXIT", ""),
+            (0x00, 0x00, 0x0f, 0x0e) if is_super => Mnemonic::new("LOW", ""),
+            (0x00, 0x00, 0x0f, 0x0f) if is_super => Mnemonic::new("HIGH", ""),
+            (0x00, _, _, _) => Mnemonic::new("SYS", format!("{:#05x}", nnn)),
+            (0x01, _, _, _) => Mnemonic::new("JP", format!("{:#05x}", nnn)),
+            (0x02, _, _, _) => Mnemonic::new("CALL", format!("{:#05x}", nnn)),
+            (0x03, _, _, _) => Mnemonic::new("SE", format!("V{:X}, {:#04x}", x, kk)),
+            (0x04, _, _, _) => Mnemonic::new("SNE", format!("V{:X}, {:#04x}", x, kk)),
+            (0x05, _, _, 0x00) => Mnemonic::new("SE", format!("V{:X}, V{:X}", x, y)),
+            (0x05, _, _, 0x02) if is_xo => Mnemonic::new("LD", format!("[I], V{:X}-V{:X}", x, y)),
+            (0x05, _, _, 0x03) if is_xo => Mnemonic::new("LD", format!("V{:X}-V{:X}, [I]", x, y)),
+            (0x06, _, _, _) => Mnemonic::new("LD", format!("V{:X}, {:#04x}", x, kk)),
+            (0x07, _, _, _) => Mnemonic::new("ADD", format!("V{:X}, {:#04x}", x, kk)),
+            (0x08, _, _, 0x00) => Mnemonic::new("LD", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x01) => Mnemonic::new("OR", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x02) => Mnemonic::new("AND", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x03) => Mnemonic::new("XOR", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x04) => Mnemonic::new("ADD", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x05) => Mnemonic::new("SUB", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x06) => Mnemonic::new("SHR", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x07) => Mnemonic::new("SUBN", format!("V{:X}, V{:X}", x, y)),
+            (0x08, _, _, 0x0e) => Mnemonic::new("SHL", format!("V{:X}, V{:X}", x, y)),
+            (0x09, _, _, 0x00) => Mnemonic::new("SNE", format!("V{:X}, V{:X}", x, y)),
+            (0x0a, _, _, _) => Mnemonic::new("LD", format!("I, {:#05x}", nnn)),
+            (0x0b, _, _, _) => Mnemonic::new("JP", format!("V0, {:#05x}", nnn)),
+            (0x0c, _, _, _) => Mnemonic::new("RND", format!("V{:X}, {:#04x}", x, kk)),
+            (0x0d, _, _, _) => Mnemonic::new("DRW", format!("V{:X}, V{:X}, {}", x, y, n)),
+            (0x0e, _, 0x09, 0x0e) => Mnemonic::new("SKP", format!("V{:X}", x)),
+            (0x0e, _, 0x0a, 0x01) => Mnemonic::new("SKNP", format!("V{:X}", x)),
+            (0x0f, 0x00, 0x00, 0x00) if is_xo => {
+                Mnemonic::new("LD", format!("I, long {:#06x}", next))
+            }
+            (0x0f, _, 0x00, 0x01) if is_xo => Mnemonic::new("PLANE", format!("{}", x)),
+            (0x0f, 0x00, 0x00, 0x02) if is_xo => Mnemonic::new("AUDIO", ""),
+            (0x0f, _, 0x00, 0x07) => Mnemonic::new("LD", format!("V{:X}, DT", x)),
+            (0x0f, _, 0x00, 0x0a) => Mnemonic::new("LD", format!("V{:X}, K", x)),
+            (0x0f, _, 0x01, 0x05) => Mnemonic::new("LD", format!("DT, V{:X}", x)),
+            (0x0f, _, 0x01, 0x08) => Mnemonic::new("LD", format!("ST, V{:X}", x)),
+            (0x0f, _, 0x01, 0x0e) => Mnemonic::new("ADD", format!("I, V{:X}", x)),
+            (0x0f, _, 0x02, 0x09) => Mnemonic::new("LD", format!("F, V{:X}", x)),
+            (0x0f, _, 0x03, 0x00) if is_super => Mnemonic::new("LD", format!("HF, V{:X}", x)),
+            (0x0f, _, 0x03, 0x03) => Mnemonic::new("LD", format!("B, V{:X}", x)),
+            (0x0f, _, 0x03, 0x0a) if is_xo => Mnemonic::new("PITCH", format!("V{:X}", x)),
+            (0x0f, _, 0x05, 0x05) => Mnemonic::new("LD", format!("[I], V{:X}", x)),
+            (0x0f, _, 0x06, 0x05) => Mnemonic::new("LD", format!("V{:X}, [I]", x)),
+            (0x0f, _, 0x07, 0x05) if is_super => Mnemonic::new("LD", format!("R, V{:X}", x)),
+            (0x0f, _, 0x08, 0x05) if is_super => Mnemonic::new("LD", format!("V{:X}, R", x)),
+            (_, _, _, _) => Mnemonic::new("DW", format!("{:#06x}", self.value)),
+        }
+    }
 }
 
 impl From<u16> for Opcode {
@@ -0,0 +1,128 @@
+use std::str::FromStr;
+
+use crate::{
+    error::ChipError,
+    properties::color::{Color, ColorPreset},
+};
+
+/// Number of colors, one per planes combination
+pub const PALETTE_SIZE: usize = 4;
+
+/// Pixel colors, indexed by the vram planes bitmask
+///
+/// 0 is the background, 1 the foreground (first plane),
+/// 2 the second plane and 3 both planes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub colors: [Color; PALETTE_SIZE],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::from(PaletteTheme::default())
+    }
+}
+
+/// Named palettes
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PaletteTheme {
+    /// White on black
+    #[default]
+    Default,
+    /// Amber monochrome monitor
+    Amber,
+    /// Green phosphor monochrome monitor
+    Green,
+    /// Greenish reflective LCD
+    Lcd,
+    /// The default colors of Octo
+    Octo,
+}
+
+impl FromStr for PaletteTheme {
+    type Err = ChipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let theme = match s {
+            "default" => Self::Default,
+            "amber" => Self::Amber,
+            "green" => Self::Green,
+            "lcd" => Self::Lcd,
+            "octo" => Self::Octo,
+            _ => {
+                return Err(ChipError::UseTheme(s.to_string()));
+            }
+        };
+
+        Ok(theme)
+    }
+}
+
+impl From<PaletteTheme> for Palette {
+    fn from(theme: PaletteTheme) -> Self {
+        let colors = match theme {
+            PaletteTheme::Default => [
+                ColorPreset::Black.into(),
+                ColorPreset::White.into(),
+                ColorPreset::Grey.into(),
+                ColorPreset::Red.into(),
+            ],
+            PaletteTheme::Amber => [
+                Color::from((0x14, 0x0c, 0x00)),
+                Color::from((0xff, 0xb0, 0x00)),
+                Color::from((0x80, 0x58, 0x00)),
+                Color::from((0xff, 0xd8, 0x80)),
+            ],
+            PaletteTheme::Green => [
+                Color::from((0x00, 0x14, 0x00)),
+                Color::from((0x33, 0xff, 0x33)),
+                Color::from((0x1a, 0x80, 0x1a)),
+                Color::from((0xb0, 0xff, 0xb0)),
+            ],
+            PaletteTheme::Lcd => [
+                Color::from((0x9b, 0xbc, 0x0f)),
+                Color::from((0x0f, 0x38, 0x0f)),
+                Color::from((0x8b, 0xac, 0x0f)),
+                Color::from((0x30, 0x62, 0x30)),
+            ],
+            PaletteTheme::Octo => [
+                Color::from((0x99, 0x66, 0x00)),
+                Color::from((0xff, 0xcc, 0x00)),
+                Color::from((0xff, 0x66, 0x00)),
+                Color::from((0x66, 0x22, 0x00)),
+            ],
+        };
+
+        Self { colors }
+    }
+}
+
+impl Palette {
+    /// Return the color of a vram pixel
+    pub fn color(&self, value: u8) -> Color {
+        self.colors[value as usize % PALETTE_SIZE]
+    }
+
+    /// Replace the first colors by `colors`
+    pub fn with_colors(mut self, colors: &[Color]) -> Self {
+        for (color, value) in self.colors.iter_mut().zip(colors) {
+            *color = *value;
+        }
+
+        self
+    }
+
+    /// Replace the background color
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.colors[0] = color;
+
+        self
+    }
+
+    /// Replace the foreground color (first plane)
+    pub fn with_foreground(mut self, color: Color) -> Self {
+        self.colors[1] = color;
+
+        self
+    }
+}
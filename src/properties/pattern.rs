@@ -0,0 +1,41 @@
+/// Pattern length in bytes
+pub const PATTERN_SIZE: usize = 16;
+
+/// Default pitch, it plays the pattern at 4000 bits per second
+pub const DEFAULT_PITCH: u8 = 64;
+
+/// XO-CHIP 1-bit audio pattern
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioPattern {
+    /// 128 samples, the most significant bit first
+    pub buffer: [u8; PATTERN_SIZE],
+    /// Playback pitch
+    pub pitch: u8,
+}
+
+impl Default for AudioPattern {
+    fn default() -> Self {
+        Self {
+            buffer: [0; PATTERN_SIZE],
+            pitch: DEFAULT_PITCH,
+        }
+    }
+}
+
+impl AudioPattern {
+    pub fn new(buffer: [u8; PATTERN_SIZE], pitch: u8) -> Self {
+        Self { buffer, pitch }
+    }
+
+    /// Return the playback rate (bits per second)
+    pub fn rate(&self) -> f32 {
+        4000.0 * 2f32.powf((self.pitch as f32 - 64.0) / 48.0)
+    }
+
+    /// Return the bit at `index` (modulo 128)
+    pub fn bit(&self, index: usize) -> bool {
+        let index = index % (PATTERN_SIZE * 8);
+
+        self.buffer[index / 8] >> (7 - index % 8) & 1 == 1
+    }
+}
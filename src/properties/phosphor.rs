@@ -0,0 +1,67 @@
+use crate::properties::{color::Color, palette::Palette, vram::Vram};
+
+/// Display filter imitating the phosphor persistence of the CRT screens
+///
+/// A lit pixel gets its color at once, a pixel going off fades to its new
+/// color, which hides the flickering of the XOR drawn sprites.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phosphor {
+    /// Part of the previous color kept at every frame, from 0 (no filter) to 1
+    decay: f32,
+    /// Displayed colors (red, green, blue)
+    pixels: Vec<[f32; 3]>,
+}
+
+impl Phosphor {
+    pub fn new(decay: f32) -> Self {
+        Self {
+            decay: decay.clamp(0.0, 0.99),
+            pixels: Vec::new(),
+        }
+    }
+
+    /// Return the part of the previous color kept at every frame
+    pub fn decay(&self) -> f32 {
+        self.decay
+    }
+
+    /// Blend the screen with the previous frames, return the colors to display
+    pub fn apply(&mut self, vram: &Vram, palette: &Palette) -> Vec<Color> {
+        let value = vram.value();
+
+        // The screen resolution has changed
+        if self.pixels.len() != value.len() {
+            self.pixels = value
+                .iter()
+                .map(|pixel| {
+                    let color = palette.color(*pixel);
+
+                    [color.r as f32, color.g as f32, color.b as f32]
+                })
+                .collect();
+        }
+
+        value
+            .iter()
+            .zip(self.pixels.iter_mut())
+            .map(|(pixel, displayed)| {
+                let color = palette.color(*pixel);
+                let target = [color.r as f32, color.g as f32, color.b as f32];
+
+                for (channel, target) in displayed.iter_mut().zip(target) {
+                    *channel = match *pixel {
+                        0 => target + (*channel - target) * self.decay,
+                        _ => target,
+                    };
+                }
+
+                Color::from((
+                    displayed[0].round() as u8,
+                    displayed[1].round() as u8,
+                    displayed[2].round() as u8,
+                    color.a,
+                ))
+            })
+            .collect()
+    }
+}
@@ -1,5 +1,5 @@
 /// Represent a rectangle (position + size)
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rectangle {
     /// x-axis
     pub x: i32,
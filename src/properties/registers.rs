@@ -0,0 +1,20 @@
+/// Snapshot of the interpreter registers
+#[derive(Debug, Clone, PartialEq)]
+pub struct Registers {
+    /// Current opcode
+    pub opcode: u16,
+    /// CPU Registers
+    pub v: [u8; 16],
+    /// Index register
+    pub i: u16,
+    /// Program count
+    pub pc: u16,
+    /// Stack
+    pub stack: [u16; 16],
+    /// Stack pointer
+    pub sp: u16,
+    /// Delay timer
+    pub delay_timer: u8,
+    /// Sound timer
+    pub sound_timer: u8,
+}
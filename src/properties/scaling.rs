@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use crate::{error::ChipError, properties::rectangle::Rectangle};
+
+/// How the screen is scaled to the window, it is always centered
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScaleMode {
+    /// Largest integer scale, the pixels stay square and sharp
+    #[default]
+    Integer,
+    /// Largest scale keeping the aspect ratio, with black borders
+    Fit,
+    /// Fill the whole window, the pixels can be stretched
+    Stretch,
+}
+
+impl FromStr for ScaleMode {
+    type Err = ChipError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mode = match s {
+            "integer" => Self::Integer,
+            "fit" => Self::Fit,
+            "stretch" => Self::Stretch,
+            _ => {
+                return Err(ChipError::UseScaleMode(s.to_string()));
+            }
+        };
+
+        Ok(mode)
+    }
+}
+
+impl ScaleMode {
+    /// Return the window area covered by a `screen` sized screen
+    pub fn area(&self, window: (u32, u32), screen: (usize, usize)) -> Rectangle {
+        let (window_w, window_h) = window;
+        let (screen_w, screen_h) = (screen.0.max(1) as u32, screen.1.max(1) as u32);
+
+        let (w, h) = match self {
+            Self::Integer => {
+                let scale = (window_w / screen_w).min(window_h / screen_h).max(1);
+
+                (screen_w * scale, screen_h * scale)
+            }
+            Self::Fit => {
+                let scale =
+                    (window_w as f64 / screen_w as f64).min(window_h as f64 / screen_h as f64);
+
+                (
+                    (screen_w as f64 * scale).round() as u32,
+                    (screen_h as f64 * scale).round() as u32,
+                )
+            }
+            Self::Stretch => (window_w, window_h),
+        };
+
+        // Centered, the borders are the same size on both sides
+        let x = (window_w as i32 - w as i32) / 2;
+        let y = (window_h as i32 - h as i32) / 2;
+
+        Rectangle::from((x, y, w, h))
+    }
+}
@@ -26,6 +26,13 @@ impl Vram {
         }
     }
 
+    /// Build from existing pixels, `value` length must be `w * h`
+    pub fn from_value(w: usize, h: usize, value: Vec<u8>) -> Self {
+        assert_eq!(value.len(), w * h);
+
+        Self { value, w, h }
+    }
+
     /// Clear the screen
     pub fn clear(&mut self) {
         for i in 0..(self.w * self.h) {
@@ -33,9 +40,43 @@ impl Vram {
         }
     }
 
+    /// Resize and clear the screen
+    pub fn resize(&mut self, w: usize, h: usize) {
+        *self = Self::new(w, h, 0);
+    }
+
+    /// Clear the pixels of the planes selected by `mask`
+    pub fn clear_planes(&mut self, mask: u8) {
+        for pixel in self.value.iter_mut() {
+            *pixel &= !mask;
+        }
+    }
+
+    /// Scroll the planes selected by `mask` of `dx` pixels right and `dy` lines down
+    ///
+    /// Negative values scroll to the left or up, the uncovered pixels are cleared
+    pub fn scroll(&mut self, dx: isize, dy: isize, mask: u8) {
+        let previous = self.value.clone();
+        let (w, h) = (self.w as isize, self.h as isize);
+
+        for y in 0..h {
+            for x in 0..w {
+                let (src_x, src_y) = (x - dx, y - dy);
+                let moved = if (0..w).contains(&src_x) && (0..h).contains(&src_y) {
+                    previous[(src_y * w + src_x) as usize] & mask
+                } else {
+                    0
+                };
+                let index = (y * w + x) as usize;
+
+                self.value[index] = (previous[index] & !mask) | moved;
+            }
+        }
+    }
+
     /// Get the value
-    pub fn value(&self) -> Vec<u8> {
-        self.value.clone()
+    pub fn value(&self) -> &[u8] {
+        &self.value
     }
 
     /// Get the width
@@ -0,0 +1,97 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::ChipError,
+    properties::{palette::Palette, vram::Vram},
+};
+
+/// Image file formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    /// Binary portable pixmap (P6)
+    Ppm,
+}
+
+impl ImageFormat {
+    /// Guess the format from the file extension, PNG by default
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("ppm") => Self::Ppm,
+            _ => Self::Png,
+        }
+    }
+}
+
+/// RGB image of the screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub w: usize,
+    pub h: usize,
+    /// Pixels, 3 bytes per pixel
+    pub rgb: Vec<u8>,
+}
+
+impl Image {
+    /// Render the screen with the palette colors, every pixel is a `scale` x `scale` square
+    pub fn from_vram(vram: &Vram, palette: &Palette, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let (w, h) = (vram.w() * scale, vram.h() * scale);
+        let value = vram.value();
+        let mut rgb = Vec::with_capacity(w * h * 3);
+
+        for y in 0..h {
+            for x in 0..w {
+                let color = palette.color(value[(y / scale) * vram.w() + x / scale]);
+
+                rgb.extend([color.r, color.g, color.b]);
+            }
+        }
+
+        Self { w, h, rgb }
+    }
+
+    /// Encode the image as PNG
+    pub fn png(&self) -> Result<Vec<u8>, ChipError> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, self.w as u32, self.h as u32);
+
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.rgb))
+            .map_err(|e| ChipError::WriteFile(e.to_string()))?;
+
+        Ok(data)
+    }
+
+    /// Encode the image as PPM
+    pub fn ppm(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.w, self.h).into_bytes();
+
+        data.extend(&self.rgb);
+        data
+    }
+
+    /// Write the image into a file, the format depends on the extension
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ChipError> {
+        let data = match ImageFormat::from_path(&path) {
+            ImageFormat::Png => self.png()?,
+            ImageFormat::Ppm => self.ppm(),
+        };
+
+        fs::write(path, data).map_err(|e| ChipError::WriteFile(e.to_string()))
+    }
+}
+
+/// Save the screen into a PNG or PPM file
+pub fn save_screenshot<P: AsRef<Path>>(
+    vram: &Vram,
+    palette: &Palette,
+    scale: usize,
+    path: P,
+) -> Result<(), ChipError> {
+    Image::from_vram(vram, palette, scale).save(path)
+}
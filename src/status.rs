@@ -0,0 +1,54 @@
+use std::{fmt, path::PathBuf};
+
+/// What the emulator tells the user, shown in the window title and given to the status callback
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    /// The ROM database has settings for the loaded program
+    DatabaseSettings(String),
+    /// The replayed movie has ended, after this amount of frames
+    ReplayEnded(u64),
+    /// The recorded movie has been written
+    MovieRecorded(PathBuf),
+    /// The window has been resized to this integer scale
+    WindowScale(u32),
+    /// The quick save slot has changed
+    SaveSlot(u8),
+    /// A video is being recorded into this file
+    VideoStarted(PathBuf),
+    /// The video recording has been stopped
+    VideoStopped,
+    /// A screenshot has been saved into this file
+    ScreenshotSaved(PathBuf),
+    /// Something went wrong, the emulation goes on if possible
+    Error(String),
+}
+
+impl Status {
+    /// Return true for the errors
+    pub fn is_error(&self) -> bool {
+        matches!(self, Status::Error(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::DatabaseSettings(name) => {
+                write!(f, "Using the ROM database settings of {}", name)
+            }
+            Status::ReplayEnded(frames) => {
+                write!(f, "End of the replay, after {} frame(s)", frames)
+            }
+            Status::MovieRecorded(path) => write!(f, "Movie recorded into {}", path.display()),
+            Status::WindowScale(scale) => write!(f, "Window scale x{}", scale),
+            Status::SaveSlot(slot) => write!(f, "Quick save slot {}", slot),
+            Status::VideoStarted(path) => write!(f, "Recording a video into {}", path.display()),
+            Status::VideoStopped => write!(f, "Video recording stopped"),
+            Status::ScreenshotSaved(path) => write!(f, "Screenshot saved into {}", path.display()),
+            Status::Error(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Called with every status, e.g. to print it
+pub type StatusCallback = Box<dyn FnMut(&Status)>;
@@ -0,0 +1,127 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::{
+    emulator::FRAME_RATE,
+    error::ChipError,
+    properties::{
+        palette::{Palette, PALETTE_SIZE},
+        vram::Vram,
+    },
+};
+
+/// GIF delays unit, in 1/100 s
+const GIF_TICKS: u64 = 100;
+
+/// Records the displayed screens into an animated GIF
+///
+/// A screen is only written when the next one arrives, to know how long it stayed.
+pub struct VideoRecorder {
+    encoder: Encoder<BufWriter<File>>,
+    /// Video size, every screen is scaled to it
+    size: (usize, usize),
+    /// Last captured screen
+    pending: Option<Vec<u8>>,
+    /// Frame the recording started at
+    start: u64,
+    /// Duration already written, in 1/100 s
+    written: u64,
+}
+
+impl VideoRecorder {
+    /// Create the GIF file, the palette colors are its global color table
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        size: (usize, usize),
+        palette: &Palette,
+        frame: u64,
+    ) -> Result<Self, ChipError> {
+        let file = File::create(path).map_err(|e| ChipError::WriteFile(e.to_string()))?;
+        let colors: Vec<u8> = palette
+            .colors
+            .iter()
+            .flat_map(|color| [color.r, color.g, color.b])
+            .collect();
+        let mut encoder = Encoder::new(BufWriter::new(file), size.0 as u16, size.1 as u16, &colors)
+            .map_err(|e| ChipError::WriteFile(e.to_string()))?;
+
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| ChipError::WriteFile(e.to_string()))?;
+
+        Ok(Self {
+            encoder,
+            size,
+            pending: None,
+            start: frame,
+            written: 0,
+        })
+    }
+
+    /// Scale the screen to the video size, the pixels become palette indexes
+    fn pixels(&self, vram: &Vram) -> Vec<u8> {
+        let (w, h) = self.size;
+        let value = vram.value();
+        let mut pixels = Vec::with_capacity(w * h);
+
+        for y in 0..h {
+            for x in 0..w {
+                let index = (y * vram.h() / h) * vram.w() + x * vram.w() / w;
+
+                pixels.push(value[index] % PALETTE_SIZE as u8);
+            }
+        }
+
+        pixels
+    }
+
+    /// Write the pending screen, if it lasted at least 1/100 s
+    ///
+    /// The delays are computed from the start, so the rounding errors do not add up.
+    fn flush(&mut self, frame: u64) -> Result<bool, ChipError> {
+        let Some(pixels) = &self.pending else {
+            return Ok(true);
+        };
+        let elapsed = (frame - self.start) * GIF_TICKS / FRAME_RATE;
+        let delay = elapsed - self.written;
+
+        if delay == 0 {
+            return Ok(false);
+        }
+
+        let mut gif_frame =
+            Frame::from_indexed_pixels(self.size.0 as u16, self.size.1 as u16, &pixels[..], None);
+
+        gif_frame.delay = delay.min(u16::MAX as u64) as u16;
+        self.encoder
+            .write_frame(&gif_frame)
+            .map_err(|e| ChipError::WriteFile(e.to_string()))?;
+        self.written = elapsed;
+
+        Ok(true)
+    }
+
+    /// Capture the screen displayed at `frame`
+    pub fn capture(&mut self, frame: u64, vram: &Vram) -> Result<(), ChipError> {
+        // A screen replaced within 1/100 s is dropped
+        self.flush(frame)?;
+        self.pending = Some(self.pixels(vram));
+
+        Ok(())
+    }
+
+    /// Write the last screen, up to `frame`, and close the file
+    pub fn finish(mut self, frame: u64) -> Result<(), ChipError> {
+        // The last screen lasts at least 1/100 s
+        let mut end = frame;
+
+        while !self.flush(end)? {
+            end += 1;
+        }
+        self.encoder
+            .into_inner()
+            .map(|_| ())
+            .map_err(|e| ChipError::WriteFile(e.to_string()))
+    }
+}
@@ -0,0 +1,153 @@
+//! Assembler round trips, the disassembled ROMs are assembled into the same bytes
+
+mod common;
+
+use std::fs;
+
+use common::path;
+use tinychip::{
+    assembler::Assembler, disassembler::Disassembler, error::ChipError,
+    interpreters::types::InterpreterType,
+};
+
+/// Every instruction form of the original CHIP-8
+const CHIP8: &str = "
+start:  cls
+        ret
+        sys 0x123
+        jp start
+        jp v0, start
+        call sub
+        se v1, 0x12
+        sne v2, 0x34
+        se v3, v4
+        sne v5, v6
+        ld v7, 0x56
+        add v8, 0x78
+        ld v9, va
+        or vb, vc
+        and vd, ve
+        xor vf, v0
+        add v1, v2
+        sub v3, v4
+        shr v5, v6
+        subn v7, v8
+        shl v9, va
+        ld i, data
+        rnd vb, 0x9a
+        drw vc, vd, 5
+        skp ve
+        sknp vf
+        ld v0, dt
+        ld v1, k
+        ld dt, v2
+        ld st, v3
+        add i, v4
+        ld f, v5
+        ld b, v6
+        ld [i], v7
+        ld v8, [i]
+sub:    ret
+data:   db 0x12, 0x34
+";
+
+/// The SCHIP instruction forms
+const SCHIP: &str = "
+        scd 4
+        scr
+        scl
+        exit
+        low
+        high
+        jp v3, 0x45
+        drw v1, v2, 0
+        ld hf, v3
+        ld r, v4
+        ld v5, r
+";
+
+/// The XO-CHIP instruction forms
+const XOCHIP: &str = "
+        scu 4
+        ld [i], v1-v3
+        ld v4-v6, [i]
+        ld i, long 0x1234
+        plane 3
+        audio
+        pitch v7
+        jp va, 0xbc
+";
+
+/// Assemble a source, disassemble it and assemble the listing
+fn round_trip(source: &str, kind: InterpreterType) {
+    let assembler = Assembler::new(kind);
+    let rom = assembler.assemble(source).unwrap();
+    let listing = Disassembler::new(kind).listing(&rom);
+
+    assert_eq!(assembler.assemble(&listing).unwrap(), rom, "{}", listing);
+}
+
+#[test]
+fn chip8_round_trip() {
+    round_trip(CHIP8, InterpreterType::Original);
+}
+
+#[test]
+fn schip_round_trip() {
+    round_trip(&format!("{}{}", CHIP8, SCHIP), InterpreterType::Schip);
+}
+
+#[test]
+fn xochip_round_trip() {
+    let source = format!("{}{}{}", CHIP8, SCHIP, XOCHIP);
+
+    round_trip(&source, InterpreterType::Xochip);
+}
+
+#[test]
+fn test_roms_round_trip() {
+    let dir = path("roms", "");
+
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+
+        // The binary ROMs have no source
+        if path.extension().is_none_or(|extension| extension != "c8s") {
+            continue;
+        }
+
+        let source = fs::read_to_string(path).unwrap();
+
+        round_trip(&source, InterpreterType::Xochip);
+    }
+}
+
+#[test]
+fn jump_vx() {
+    let schip = Assembler::new(InterpreterType::Schip);
+
+    assert_eq!(schip.assemble("jp v3, 0x45").unwrap(), [0xb3, 0x45]);
+    assert_eq!(schip.assemble("jp v0, 0x345").unwrap(), [0xb3, 0x45]);
+    assert!(matches!(
+        schip.assemble("jp v3, 0x345"),
+        Err(ChipError::Assemble { line: 1, .. })
+    ));
+    assert!(matches!(
+        Assembler::new(InterpreterType::Original).assemble("jp v3, 0x45"),
+        Err(ChipError::Assemble { line: 1, .. })
+    ));
+}
+
+#[test]
+fn empty_operand() {
+    let assembler = Assembler::new(InterpreterType::Original);
+
+    for source in ["ld v0,, 1", "drw v1, v2,", "ld v0, ,5", "cls\nadd v0, "] {
+        let line = source.lines().count();
+
+        assert!(matches!(
+            assembler.assemble(source),
+            Err(ChipError::Assemble { line: l, .. }) if l == line
+        ));
+    }
+}
@@ -0,0 +1,33 @@
+//! Helpers shared by the integration tests
+
+// Every test crate uses only some of them
+#![allow(dead_code)]
+
+use std::{fs, path::PathBuf};
+
+use tinychip::{assembler::Assembler, interpreters::types::InterpreterType};
+
+/// Return the path of a file of the `tests` directory
+pub fn path(dir: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join(dir)
+        .join(name)
+}
+
+/// Assemble a test ROM of `tests/roms`
+pub fn rom(name: &str, kind: InterpreterType) -> Vec<u8> {
+    let source = fs::read_to_string(path("roms", &format!("{}.c8s", name))).unwrap();
+
+    Assembler::new(kind).assemble(&source).unwrap()
+}
+
+/// Read the IBM logo ROM of `tests/roms`
+pub fn ibm_logo() -> Vec<u8> {
+    fs::read(path("roms", "ibm_logo.ch8")).unwrap()
+}
+
+/// Return the path of a fixture of `tests/fixtures`
+pub fn fixture(name: &str) -> PathBuf {
+    path("fixtures", &format!("{}.txt", name))
+}
@@ -0,0 +1,46 @@
+//! Bundled ROM database
+
+mod common;
+
+use common::ibm_logo;
+use tinychip::{
+    database::RomDatabase,
+    interpreters::{quirks::QuirksPreset, types::InterpreterType},
+};
+
+/// SHA-1 of the IBM logo ROM
+const IBM_LOGO: &str = "1ba58656810b67fd131eb9af3e3987863bf26c90";
+
+#[test]
+fn bundled_is_not_empty() {
+    assert!(!RomDatabase::bundled().is_empty());
+}
+
+#[test]
+fn bundled_resolves_hash() {
+    let database = RomDatabase::bundled();
+    let settings = database.get_hash(IBM_LOGO).unwrap();
+
+    assert_eq!(settings.name.as_deref(), Some("IBM Logo"));
+    assert_eq!(settings.interpreter, Some(InterpreterType::Original));
+    assert_eq!(settings.quirks.preset, Some(QuirksPreset::Vip));
+    assert_eq!(settings.clock, Some(500));
+
+    // The hashes are case insensitive
+    assert!(database.get_hash(&IBM_LOGO.to_uppercase()).is_some());
+}
+
+#[test]
+fn bundled_resolves_rom() {
+    let program = ibm_logo();
+
+    assert_eq!(RomDatabase::hash(&program), IBM_LOGO);
+    assert_eq!(
+        RomDatabase::bundled()
+            .get(&program)
+            .unwrap()
+            .name
+            .as_deref(),
+        Some("IBM Logo")
+    );
+}
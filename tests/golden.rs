@@ -0,0 +1,147 @@
+//! Golden-image tests, the test ROMs are assembled from `tests/roms` and their
+//! screens compared with the ASCII-art fixtures of `tests/fixtures`
+//!
+//! `ibm_logo.ch8` is the well-known IBM logo ROM, the others are written for
+//! this repository.
+//!
+//! Run with `TINYCHIP_UPDATE_FIXTURES=1` to write the fixtures again.
+
+mod common;
+
+use common::{fixture, path, rom};
+use tinychip::{
+    event::{Hotkey, Input},
+    harness::Harness,
+    interpreters::{
+        quirks::{Quirks, QuirksPreset},
+        types::InterpreterType,
+    },
+};
+
+/// Run a test ROM and compare its screen with the fixture of the same name
+fn golden(name: &str, kind: InterpreterType, quirks: Quirks, cycles: u64) {
+    Harness::new(kind)
+        .set_quirks(quirks)
+        .load(rom(name, kind))
+        .unwrap()
+        .run(cycles)
+        .assert_fixture(fixture(name));
+}
+
+#[test]
+fn ibm_logo() {
+    Harness::new(InterpreterType::Original)
+        .load_file(path("roms", "ibm_logo.ch8"))
+        .unwrap()
+        .run(100)
+        .assert_fixture(fixture("ibm_logo"));
+}
+
+#[test]
+fn font() {
+    golden("font", InterpreterType::Original, Quirks::default(), 500);
+}
+
+#[test]
+fn flags() {
+    golden("flags", InterpreterType::Original, Quirks::default(), 1000);
+}
+
+#[test]
+fn flags_vip() {
+    // Every check avoids the quirks, so the marks are the same
+    Harness::new(InterpreterType::Original)
+        .set_quirks(QuirksPreset::Vip.into())
+        .load(rom("flags", InterpreterType::Original))
+        .unwrap()
+        .run(5000)
+        .assert_fixture(fixture("flags"));
+}
+
+#[test]
+fn quirks() {
+    let presets = [
+        ("quirks_vip", QuirksPreset::Vip),
+        ("quirks_schip", QuirksPreset::Schip),
+        ("quirks_xochip", QuirksPreset::Xochip),
+        ("quirks_modern", QuirksPreset::Modern),
+    ];
+
+    for (name, preset) in presets {
+        Harness::new(InterpreterType::Original)
+            .set_quirks(preset.into())
+            .load(rom("quirks", InterpreterType::Original))
+            .unwrap()
+            .run(2000)
+            .assert_fixture(fixture(name));
+    }
+}
+
+#[test]
+fn sprites() {
+    let quirks = [("sprites_wrap", false), ("sprites_clip", true)];
+
+    for (name, clip) in quirks {
+        Harness::new(InterpreterType::Original)
+            .set_quirks(Quirks {
+                clip,
+                ..Quirks::default()
+            })
+            .load(rom("sprites", InterpreterType::Original))
+            .unwrap()
+            .run(200)
+            .assert_fixture(fixture(name));
+    }
+}
+
+#[test]
+fn display_wait() {
+    let quirks = [("display_wait_off", false), ("display_wait_on", true)];
+
+    // 10 frames of 8 cycles, the quirk draws a dot per frame
+    for (name, display_wait) in quirks {
+        Harness::new(InterpreterType::Original)
+            .set_quirks(Quirks {
+                display_wait,
+                ..Quirks::default()
+            })
+            .load(rom("display_wait", InterpreterType::Original))
+            .unwrap()
+            .run(80)
+            .assert_fixture(fixture(name));
+    }
+}
+
+#[test]
+fn hires() {
+    golden("hires", InterpreterType::Schip, Quirks::default(), 100);
+}
+
+/// The `5` key is pressed at the frame 1 and held until the frame 12 (cycles 8 to 96)
+fn keypad(quirks: Quirks) -> Harness {
+    Harness::new(InterpreterType::Original)
+        .set_quirks(quirks)
+        .set_inputs(vec![(1, vec![Input::Hotkey(Hotkey::Z)]), (12, vec![])])
+        .load(rom("keypad", InterpreterType::Original))
+        .unwrap()
+}
+
+#[test]
+fn keypad_press() {
+    let mut harness = keypad(Quirks::default());
+
+    // A held key is given only once
+    harness.run(50).assert_fixture(fixture("keypad"));
+    harness.run(100).assert_fixture(fixture("keypad"));
+}
+
+#[test]
+fn keypad_release() {
+    let mut harness = keypad(Quirks {
+        key_release: true,
+        ..Quirks::default()
+    });
+
+    harness.run(50).assert_fixture(fixture("empty"));
+    harness.run(100).assert_fixture(fixture("keypad"));
+}
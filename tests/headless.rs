@@ -0,0 +1,160 @@
+//! Headless runs, the framebuffer of the backend is read back from the emulator
+
+mod common;
+
+use std::{cell::RefCell, env, fs, path::PathBuf, rc::Rc};
+
+use common::{fixture, ibm_logo, path, rom};
+use tinychip::{
+    apis::api::ApiKind,
+    database::RomSettings,
+    emulator::{Emulator, EmulatorBuilder},
+    error::ChipError,
+    interpreters::{quirks::Quirks, types::InterpreterType},
+    models::{core::Core, interpreter::Interpreter},
+    movie::Movie,
+    properties::palette::Palette,
+    status::Status,
+};
+
+/// Compare the framebuffer of a 320x160 window with a fixture
+fn assert_frame(emulator: &Emulator, name: &str) {
+    let fixture = fs::read_to_string(fixture(name)).unwrap();
+    let frame = emulator.headless().unwrap().frame();
+    let palette = Palette::default();
+
+    // The 64x32 screen is scaled 5 times to fill the window
+    assert_eq!(frame.len(), 320 * 160);
+    for (y, line) in fixture.lines().enumerate() {
+        for (x, pixel) in line.chars().enumerate() {
+            let expected = palette.color((pixel == '#') as u8);
+
+            for (dx, dy) in (0..5).flat_map(|dx| (0..5).map(move |dy| (dx, dy))) {
+                assert_eq!(frame[(y * 5 + dy) * 320 + x * 5 + dx], expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn logo_framebuffer() {
+    let program = ibm_logo();
+
+    let mut emulator = EmulatorBuilder::new()
+        .set_api(ApiKind::Headless)
+        .set_window_size((320, 160))
+        .set_max_frames(10)
+        .build();
+
+    emulator.load(program).unwrap();
+    emulator.run();
+
+    let headless = emulator.headless().unwrap();
+
+    assert_eq!(headless.frames(), 10);
+    assert!(!headless.is_beeping());
+    assert_frame(&emulator, "ibm_logo");
+}
+
+#[test]
+fn settings_keep_quirks() {
+    let program = rom("sprites", InterpreterType::Schip);
+    let mut interpreter: Box<dyn Interpreter> = InterpreterType::Original.into();
+
+    interpreter.set_quirks(Quirks {
+        clip: true,
+        ..Quirks::default()
+    });
+
+    let mut emulator = EmulatorBuilder::new()
+        .set_api(ApiKind::Headless)
+        .set_window_size((320, 160))
+        .set_max_frames(10)
+        .set_interpreter(interpreter)
+        .build();
+
+    // A database entry replaces the interpreter, the clipping is kept
+    emulator.apply_settings(&RomSettings {
+        interpreter: Some(InterpreterType::Schip),
+        ..RomSettings::default()
+    });
+    emulator.load(program).unwrap();
+    emulator.run();
+
+    assert_frame(&emulator, "sprites_clip");
+}
+
+#[test]
+fn movie_statuses() {
+    let movie = env::temp_dir().join("tinychip_movie_statuses.c8m");
+    let statuses = Rc::new(RefCell::new(Vec::new()));
+    let reported = statuses.clone();
+
+    let mut recorder = EmulatorBuilder::new()
+        .set_api(ApiKind::Headless)
+        .set_max_frames(5)
+        .set_record(&movie)
+        .set_status_callback(move |status| reported.borrow_mut().push(status.clone()))
+        .build();
+
+    recorder.load(ibm_logo()).unwrap();
+    recorder.run();
+
+    let reported = statuses.clone();
+    let mut player = EmulatorBuilder::new()
+        .set_api(ApiKind::Headless)
+        .set_max_frames(10)
+        .set_replay(Movie::from_file(&movie).unwrap())
+        .set_status_callback(move |status| reported.borrow_mut().push(status.clone()))
+        .build();
+
+    player.load(ibm_logo()).unwrap();
+    player.run();
+    fs::remove_file(&movie).unwrap();
+
+    assert_eq!(
+        *statuses.borrow(),
+        vec![Status::MovieRecorded(movie), Status::ReplayEnded(5)]
+    );
+}
+
+#[test]
+fn replay_another_rom() {
+    let movie = env::temp_dir().join("tinychip_replay_another_rom.c8m");
+    let mut recorder = EmulatorBuilder::new()
+        .set_api(ApiKind::Headless)
+        .set_max_frames(5)
+        .set_record(&movie)
+        .build();
+
+    recorder.load(ibm_logo()).unwrap();
+    recorder.run();
+
+    let mut player = EmulatorBuilder::new()
+        .set_api(ApiKind::Headless)
+        .set_replay(Movie::from_file(&movie).unwrap())
+        .build();
+
+    fs::remove_file(&movie).unwrap();
+    assert!(matches!(
+        player.load(vec![0x12, 0x00]),
+        Err(ChipError::LoadMovie(_))
+    ));
+}
+
+#[test]
+fn rom_path_after_load() {
+    let mut emulator = EmulatorBuilder::new().set_api(ApiKind::Headless).build();
+
+    // A missing file does not name the quick saves
+    assert!(matches!(
+        emulator.load_from_file(path("roms", "missing.ch8")),
+        Err(ChipError::ReadFile(_))
+    ));
+    assert_eq!(emulator.state_path(0), PathBuf::from("tinychip.state0"));
+
+    emulator
+        .load_from_file(path("roms", "ibm_logo.ch8"))
+        .unwrap();
+    assert_eq!(emulator.state_path(0), path("roms", "ibm_logo.ch8.state0"));
+}
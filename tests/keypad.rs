@@ -0,0 +1,40 @@
+//! Keypad edges given to the interpreter
+
+use tinychip::{
+    event::{Hotkey, Input, KeyEvent},
+    interpreters::{quirks::Quirks, types::InterpreterType},
+    models::interpreter::Interpreter,
+};
+
+/// Wait for a key into V0 (fx0a), then loop forever
+const WAIT_KEY: [u8; 4] = [0xf0, 0x0a, 0x12, 0x02];
+
+/// Return the V0 register after a key tapped within a single frame
+fn tapped(key_release: bool) -> u8 {
+    let mut interpreter: Box<dyn Interpreter> = InterpreterType::Original.into();
+    let key = Input::Hotkey(Hotkey::Z);
+
+    interpreter.set_quirks(Quirks {
+        key_release,
+        ..Quirks::default()
+    });
+    interpreter.load_program(WAIT_KEY.to_vec()).unwrap();
+
+    // Reach fx0a, then press and release the key before the next cycle
+    interpreter.step(&[]);
+    interpreter.step(&[KeyEvent::Down(key), KeyEvent::Up(key)]);
+    interpreter.step(&[]);
+
+    interpreter.registers().v[0]
+}
+
+#[test]
+fn tap_within_a_frame() {
+    let interpreter: Box<dyn Interpreter> = InterpreterType::Original.into();
+    let expected = interpreter.keymap().key(Hotkey::Z).unwrap();
+
+    assert_ne!(expected, 0);
+
+    assert_eq!(tapped(false), expected);
+    assert_eq!(tapped(true), expected);
+}
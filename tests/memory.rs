@@ -0,0 +1,25 @@
+//! Program loading, the program must fit after the interpreter area
+
+use tinychip::{
+    error::ChipError, interpreters::types::InterpreterType, models::interpreter::Interpreter,
+};
+
+/// Return the result of loading a program of `size` bytes
+fn load(kind: InterpreterType, size: usize) -> Result<(), ChipError> {
+    let mut interpreter: Box<dyn Interpreter> = kind.into();
+
+    interpreter.load_program(vec![0; size])
+}
+
+#[test]
+fn oversized_program() {
+    // 4 KiB of memory, 64 KiB with XO-CHIP
+    for (kind, max) in [
+        (InterpreterType::Original, 0x1000 - 0x200),
+        (InterpreterType::Schip, 0x1000 - 0x200),
+        (InterpreterType::Xochip, 0x10000 - 0x200),
+    ] {
+        assert!(load(kind, max).is_ok());
+        assert!(matches!(load(kind, max + 1), Err(ChipError::LoadProgram)));
+    }
+}
@@ -0,0 +1,33 @@
+//! Program counter steps
+
+use tinychip::interpreters::pc::{ProgramCount, ProgramCountState};
+
+#[test]
+fn next_wraps_at_the_top_of_memory() {
+    let mut pc = ProgramCount::from(0xfffe);
+
+    pc.step();
+    assert_eq!(pc.value, 0);
+}
+
+#[test]
+fn skip_wraps_at_the_top_of_memory() {
+    let mut pc = ProgramCount::from(0xfffe);
+
+    pc.set_state(ProgramCountState::Skip);
+    pc.step();
+    assert_eq!(pc.value, 2);
+}
+
+#[test]
+fn jump_at_the_top_of_memory() {
+    let mut pc = ProgramCount::from(0x200);
+
+    pc.set_state(ProgramCountState::Jump(0xfffe));
+    pc.step();
+    assert_eq!(pc.value, 0xfffe);
+
+    pc.reset_state();
+    pc.step();
+    assert_eq!(pc.value, 0);
+}
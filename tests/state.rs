@@ -0,0 +1,94 @@
+//! Save states
+
+use tinychip::{interpreters::types::InterpreterType, models::interpreter::Interpreter};
+
+/// Draw random numbers into V0 - V3 forever
+const RANDOM: [u8; 10] = [0xc0, 0xff, 0xc1, 0xff, 0xc2, 0xff, 0xc3, 0xff, 0x12, 0x00];
+
+/// Offset of the stack pointer: header (26), memory size and memory (4 + 4096),
+/// opcode (2), V registers (16), I (2), program count (2) and stack (32)
+const SP_OFFSET: usize = 4180;
+
+/// Return an interpreter which ran `RANDOM` for a few cycles
+fn running(seed: u64) -> Box<dyn Interpreter> {
+    let mut interpreter: Box<dyn Interpreter> = InterpreterType::Original.into();
+
+    interpreter.set_seed(seed);
+    interpreter.load_program(RANDOM.to_vec()).unwrap();
+    for _ in 0..25 {
+        interpreter.step(&[]);
+    }
+
+    interpreter
+}
+
+/// Run `cycles` cycles and return the V registers
+fn run(interpreter: &mut dyn Interpreter, cycles: usize) -> [u8; 16] {
+    for _ in 0..cycles {
+        interpreter.step(&[]);
+    }
+
+    interpreter.registers().v
+}
+
+#[test]
+fn restored_run_does_not_diverge() {
+    let mut original = running(1);
+    let state = original.save_state();
+
+    // Another seed, the generator position comes from the state
+    let mut restored = running(2);
+
+    restored.load_state(&state).unwrap();
+
+    for _ in 0..10 {
+        assert_eq!(run(original.as_mut(), 8), run(restored.as_mut(), 8));
+    }
+}
+
+#[test]
+fn invalid_stack_pointer() {
+    let mut interpreter = running(0);
+    let mut state = interpreter.save_state();
+
+    // A full stack is valid
+    state[SP_OFFSET..SP_OFFSET + 2].copy_from_slice(&16u16.to_be_bytes());
+    assert!(interpreter.load_state(&state).is_ok());
+
+    state[SP_OFFSET..SP_OFFSET + 2].copy_from_slice(&17u16.to_be_bytes());
+    assert!(interpreter.load_state(&state).is_err());
+}
+
+#[test]
+fn invalid_screen_size() {
+    let mut interpreter = running(0);
+    let state = interpreter.save_state();
+    // The screen size is followed by the pixels and the audio pattern flag
+    let offset = state.len() - 1 - 64 * 32 - 4;
+
+    for size in [[0, 0, 0, 32], [0, 64, 0, 0], [0, 64, 0, 33]] {
+        let mut state = state.clone();
+
+        state[offset..offset + 4].copy_from_slice(&size);
+        assert!(interpreter.load_state(&state).is_err());
+    }
+}
+
+#[test]
+fn unused_plane_bits() {
+    let mut interpreter: Box<dyn Interpreter> = InterpreterType::Xochip.into();
+
+    // plane 15, then loop forever
+    let program = vec![0xff, 0x01, 0x12, 0x02];
+
+    interpreter.load_program(program.clone()).unwrap();
+    for _ in 0..4 {
+        interpreter.step(&[]);
+    }
+
+    let state = interpreter.save_state();
+    let mut restored: Box<dyn Interpreter> = InterpreterType::Xochip.into();
+
+    restored.load_program(program).unwrap();
+    assert!(restored.load_state(&state).is_ok());
+}